@@ -0,0 +1,110 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use multibase::Base;
+#[cfg(feature = "base58check")]
+use multiutil::Base58CheckEncoder;
+use multiutil::{
+    base_marker, BareEncoder, Base58Encoder, BaseEncoded, BaseEncoder, DetectedEncoder,
+    FixedEncoder, MultibaseEncoder, Varbytes,
+};
+#[cfg(feature = "bech32")]
+use multiutil::{Bech32Encoder, Bech32Hrp};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// payload sizes in bytes
+const SIZES: [usize; 3] = [32, 256, 4096];
+
+#[cfg(feature = "bech32")]
+struct Bench;
+
+#[cfg(feature = "bech32")]
+impl Bech32Hrp for Bench {
+    const HRP: &'static str = "bench";
+}
+
+fn payload(size: usize) -> Varbytes {
+    Varbytes((0..size).map(|i| (i * 31 % 251) as u8).collect())
+}
+
+/// Display, Hash and TryFrom<&str> of a BaseEncoded with the given encoder
+fn bench_encoder<Enc: BaseEncoder>(c: &mut Criterion, name: &str, base: Base, sizes: &[usize]) {
+    let mut group = c.benchmark_group(format!("base_encoded/{}", name));
+    for &size in sizes {
+        let v = BaseEncoded::<Varbytes, Enc>::new(base, payload(size));
+        let s = v.to_string();
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("display", size), &v, |b, v| {
+            b.iter(|| black_box(v).to_string())
+        });
+        group.bench_with_input(BenchmarkId::new("hash", size), &v, |b, v| {
+            b.iter(|| {
+                let mut state = DefaultHasher::new();
+                black_box(v).hash(&mut state);
+                state.finish()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("try_from", size), &s, |b, s| {
+            b.iter(|| BaseEncoded::<Varbytes, Enc>::try_from(black_box(s.as_str())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_encoders(c: &mut Criterion) {
+    for base in [
+        Base::Base16Lower,
+        Base::Base32Lower,
+        Base::Base36Lower,
+        Base::Base58Btc,
+        Base::Base64Url,
+    ] {
+        let name = format!("multibase_{}", multiutil::base_name(base).to_lowercase());
+        bench_encoder::<MultibaseEncoder>(c, &name, base, &SIZES);
+    }
+    bench_encoder::<Base58Encoder>(c, "base58", Base::Base58Btc, &SIZES);
+    bench_encoder::<BareEncoder<base_marker::Base32Lower>>(
+        c,
+        "bare_base32lower",
+        Base::Base32Lower,
+        &SIZES,
+    );
+    bench_encoder::<FixedEncoder<base_marker::Base64>>(c, "fixed_base64", Base::Base64, &SIZES);
+    bench_encoder::<DetectedEncoder>(c, "detected_base58btc", Base::Base58Btc, &SIZES);
+    #[cfg(feature = "base58check")]
+    bench_encoder::<Base58CheckEncoder>(c, "base58check", Base::Base58Btc, &SIZES);
+    // bech32 decoding rejects strings longer than 90 characters, which only
+    // the smallest payload fits in
+    #[cfg(feature = "bech32")]
+    bench_encoder::<Bech32Encoder<Bench>>(c, "bech32", Base::Base32Lower, &SIZES[..1]);
+}
+
+/// DetectedEncoder tries every base in BaseIter when a string has no valid
+/// multibase prefix
+fn bench_detection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("detected");
+    for size in [32, 256] {
+        // a valid multibase string is decoded on the first attempt
+        let prefixed = multibase::encode(Base::Base58Btc, payload(size).as_slice());
+        // decimal digits are a valid bare encoding in most bases
+        let ambiguous: String = "123456789".chars().cycle().take(size).collect();
+        // no base accepts these so every attempt fails
+        let undetectable = "!".repeat(size);
+
+        for (name, s) in [
+            ("prefixed", prefixed),
+            ("ambiguous", ambiguous),
+            ("undetectable", undetectable),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, size), &s, |b, s| {
+                b.iter(|| DetectedEncoder::from_base_encoded(black_box(s)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encoders, bench_detection);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use multibase::Base;
+use multicodec::Codec;
+use multiutil::{CodecPayload, Varbytes, Varmap, Varuint};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// serialize and deserialize a value with CBOR and JSON
+fn bench_round_trip<T>(c: &mut Criterion, name: &str, t: &T)
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut group = c.benchmark_group(format!("serde/{}", name));
+    let cbor = serde_cbor::to_vec(t).unwrap();
+    let json = serde_json::to_string(t).unwrap();
+    group.bench_function("cbor_serialize", |b| {
+        b.iter(|| serde_cbor::to_vec(black_box(t)).unwrap())
+    });
+    group.bench_function("cbor_deserialize", |b| {
+        b.iter(|| serde_cbor::from_slice::<T>(black_box(&cbor)).unwrap())
+    });
+    group.bench_function("json_serialize", |b| {
+        b.iter(|| serde_json::to_string(black_box(t)).unwrap())
+    });
+    group.bench_function("json_deserialize", |b| {
+        b.iter(|| serde_json::from_str::<T>(black_box(&json)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_wrappers(c: &mut Criterion) {
+    let data: Vec<u8> = (0..256_u32).map(|i| (i * 31 % 251) as u8).collect();
+
+    bench_round_trip(c, "varuint", &Varuint(0x0fff_ffff_u64));
+    bench_round_trip(c, "varbytes", &Varbytes(data.clone()));
+    bench_round_trip(
+        c,
+        "encoded_varbytes",
+        &Varbytes::encoded_new(Base::Base58Btc, data.clone()),
+    );
+    bench_round_trip(
+        c,
+        "codec_payload",
+        &CodecPayload::new(Codec::Sha2256, data[..32].to_vec()),
+    );
+    let map: Varmap = (0..16_u64).map(|k| (k, data[..32].to_vec())).collect();
+    bench_round_trip(c, "varmap", &map);
+}
+
+criterion_group!(benches, bench_wrappers);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use multitrait::TryDecodeFrom;
+use multiutil::{EncodedLen, Varuint, VaruintRepr};
+
+/// encode into a Vec, encode into a slice and decode one varuint
+fn bench_value<T>(c: &mut Criterion, name: &str, values: &[T])
+where
+    T: VaruintRepr + Copy,
+    Varuint<T>: EncodedLen,
+{
+    let mut group = c.benchmark_group(format!("varuint/{}", name));
+    for &t in values {
+        let v = Varuint(t);
+        let len = EncodedLen::encoded_len(&v);
+        let b: Vec<u8> = v.into();
+        group.bench_with_input(BenchmarkId::new("to_vec", len), &v, |bench, v| {
+            bench.iter(|| Vec::from(*black_box(v)))
+        });
+        group.bench_with_input(BenchmarkId::new("encode_to_slice", len), &v, |bench, v| {
+            let mut buf = [0u8; 19];
+            bench.iter(|| black_box(v).encode_to_slice(&mut buf).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("try_decode_from", len), &b, |bench, b| {
+            bench.iter(|| Varuint::<T>::try_decode_from(black_box(b)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_widths(c: &mut Criterion) {
+    bench_value(c, "u8", &[0x7f, u8::MAX]);
+    bench_value(c, "u16", &[0x7f, u16::MAX]);
+    bench_value(c, "u32", &[0x7f, 0x3fff, u32::MAX]);
+    bench_value(c, "u64", &[0x7f, 0x3fff, 0x0fff_ffff, u64::MAX]);
+    bench_value(c, "usize", &[0x7f, 0x3fff, usize::MAX]);
+    bench_value(c, "u128", &[0x7f, u64::MAX.into(), u128::MAX]);
+}
+
+criterion_group!(benches, bench_widths);
+criterion_main!(benches);
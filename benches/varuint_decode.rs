@@ -0,0 +1,72 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::{decode_u64, decode_usize, Varbytes};
+
+/// values with varuint encodings of 1, 2, 4, 8 and 10 bytes
+const VALUES: [u64; 5] = [0x7f, 0x3fff, 0x0fff_ffff, 0x00ff_ffff_ffff_ffff, u64::MAX];
+
+fn bench_u64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_u64");
+    for n in VALUES {
+        // pad so the fast path always has a full word to load
+        let mut b = n.encode_into();
+        let len = b.len();
+        b.extend_from_slice(&[0u8; 8]);
+        group.bench_with_input(BenchmarkId::new("scalar", len), &b, |bench, b| {
+            bench.iter(|| u64::try_decode_from(black_box(b)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("fast", len), &b, |bench, b| {
+            bench.iter(|| decode_u64(black_box(b)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_records(c: &mut Criterion) {
+    // a buffer of length-prefixed records like the ones Varbytes decodes, with
+    // random lengths so the prefix widths are not predictable
+    let mut buf = Vec::new();
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    for _ in 0..10_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        buf.extend(Varbytes(vec![0xa5; (state % 300) as usize]).encode_into());
+    }
+
+    let mut group = c.benchmark_group("decode_records");
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("scalar", |bench| {
+        bench.iter(|| {
+            let mut ptr = black_box(buf.as_slice());
+            while !ptr.is_empty() {
+                let (len, rest) = usize::try_decode_from(ptr).unwrap();
+                ptr = &rest[len..];
+            }
+        })
+    });
+    group.bench_function("fast", |bench| {
+        bench.iter(|| {
+            let mut ptr = black_box(buf.as_slice());
+            while !ptr.is_empty() {
+                let (len, rest) = decode_usize(ptr).unwrap();
+                ptr = &rest[len..];
+            }
+        })
+    });
+    group.bench_function("varbytes", |bench| {
+        bench.iter(|| {
+            let mut ptr = black_box(buf.as_slice());
+            while !ptr.is_empty() {
+                let (v, rest) = Varbytes::try_decode_from(ptr).unwrap();
+                black_box(v);
+                ptr = rest;
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_u64, bench_records);
+criterion_main!(benches);
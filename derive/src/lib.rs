@@ -0,0 +1,115 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! multiutil-derive
+#![warn(missing_docs)]
+#![deny(
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{parse_macro_input, token::Paren, Data, DeriveInput, Error, Fields, Ident};
+
+/// the integer types a fieldless enum can be varuint encoded as
+const REPRS: [&str; 6] = ["u8", "u16", "u32", "u64", "u128", "usize"];
+
+/// Implements `multiutil::VaruintRepr` for a fieldless enum with an unsigned
+/// `#[repr(..)]` so that it can be wrapped in a `multiutil::Varuint`. The
+/// enum is encoded as its discriminant and decoding any other value fails with
+/// `VaruintError::UnknownDiscriminant`.
+#[proc_macro_derive(VaruintRepr)]
+pub fn derive_varuint_repr(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    varuint_repr(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn varuint_repr(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "VaruintRepr can only be derived for enums",
+            ))
+        }
+    };
+    if let Some(v) = data
+        .variants
+        .iter()
+        .find(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return Err(Error::new_spanned(
+            &v.ident,
+            "VaruintRepr can only be derived for fieldless enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            name,
+            "VaruintRepr can't be derived for an enum without variants",
+        ));
+    }
+    let repr = repr(input)?;
+    let variants = data.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let target = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::multiutil::VaruintRepr for #name #ty_generics #where_clause {
+            type Repr = #repr;
+
+            fn to_repr(&self) -> #repr {
+                match self {
+                    #(Self::#variants => Self::#variants as #repr),*
+                }
+            }
+
+            fn from_repr(repr: #repr) -> ::core::result::Result<Self, ::multiutil::Error> {
+                #(
+                    if repr == Self::#variants as #repr {
+                        return ::core::result::Result::Ok(Self::#variants);
+                    }
+                )*
+                ::core::result::Result::Err(
+                    ::multiutil::error::VaruintError::UnknownDiscriminant {
+                        value: ::std::string::ToString::to_string(&repr),
+                        target: #target,
+                    }
+                    .into(),
+                )
+            }
+        }
+    })
+}
+
+/// find the unsigned integer in the enum's #[repr(..)] attribute
+fn repr(input: &DeriveInput) -> Result<Ident, Error> {
+    let mut repr = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            match meta.path.get_ident() {
+                Some(ident) if REPRS.contains(&ident.to_string().as_str()) => {
+                    repr = Some(ident.clone());
+                }
+                // skip the arguments of e.g. align(8)
+                _ if meta.input.peek(Paren) => {
+                    meta.input.parse::<TokenTree>()?;
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+    }
+    repr.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "VaruintRepr needs an unsigned #[repr(..)], e.g. #[repr(u64)]",
+        )
+    })
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Idnetifier: Apache-2.0
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use multiutil::{BaseEncoder, MultibaseEncoder};
+
+fuzz_target!(|s: &str| {
+    if let Ok(decodings) = MultibaseEncoder::from_base_encoded(s) {
+        for (base, data) in decodings {
+            // decoding the re-encoded data gives back the same base and data
+            let s2 = MultibaseEncoder::to_base_encoded(base, &data);
+            let decodings2 = MultibaseEncoder::from_base_encoded(&s2).unwrap();
+            assert!(decodings2.contains(&(base, data)));
+        }
+    }
+});
@@ -0,0 +1,13 @@
+// SPDX-License-Idnetifier: Apache-2.0
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use multiutil::EncodedVarbytes;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(v) = serde_cbor::from_slice::<EncodedVarbytes>(data) {
+        // serializing and deserializing again is idempotent
+        let b = serde_cbor::to_vec(&v).unwrap();
+        let v2: EncodedVarbytes = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(v, v2);
+    }
+});
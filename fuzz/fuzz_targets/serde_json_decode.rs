@@ -0,0 +1,20 @@
+// SPDX-License-Idnetifier: Apache-2.0
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use multiutil::{prelude::Base, EncodedVarbytes, EncodingInfo};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(v) = serde_json::from_slice::<EncodedVarbytes>(data) {
+        // the Identity base can only encode UTF-8 and the varbytes may have
+        // been cut from the middle of a multi-byte character
+        let b: Vec<u8> = (*v).clone().into();
+        if v.encoding() == Base::Identity && std::str::from_utf8(&b).is_err() {
+            return;
+        }
+
+        // serializing and deserializing again is idempotent
+        let s = serde_json::to_string(&v).unwrap();
+        let v2: EncodedVarbytes = serde_json::from_str(&s).unwrap();
+        assert_eq!(v, v2);
+    }
+});
@@ -0,0 +1,19 @@
+// SPDX-License-Idnetifier: Apache-2.0
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::Varbytes;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((v, ptr)) = Varbytes::try_decode_from(data) {
+        // the length prefix decoder rejects non-minimal and overflowing
+        // encodings so the only accepted encoding is the canonical one
+        let b = v.encode_into();
+        assert_eq!(&data[..data.len() - ptr.len()], b.as_slice());
+
+        // decoding the re-encoding gives back the same value
+        let (v2, ptr2) = Varbytes::try_decode_from(&b).unwrap();
+        assert_eq!(v, v2);
+        assert!(ptr2.is_empty());
+    }
+});
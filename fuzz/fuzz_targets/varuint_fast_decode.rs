@@ -0,0 +1,17 @@
+// SPDX-License-Idnetifier: Apache-2.0
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use multitrait::TryDecodeFrom;
+use multiutil::{decode_u64, decode_usize};
+
+fuzz_target!(|data: &[u8]| {
+    // the fast decoders must agree with the scalar ones on every input
+    assert_eq!(
+        u64::try_decode_from(data).map_err(|e| e.to_string()),
+        decode_u64(data).map_err(|e| e.to_string())
+    );
+    assert_eq!(
+        usize::try_decode_from(data).map_err(|e| e.to_string()),
+        decode_usize(data).map_err(|e| e.to_string())
+    );
+});
@@ -0,0 +1,93 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`Arbitrary`] impls so that types embedding [`crate::Varuint`],
+//! [`crate::Varbytes`] and [`crate::BaseEncoded`] can derive `Arbitrary`
+use crate::{prelude::Base, BaseEncoded, BaseEncoder, BaseIter, EncodingInfo, Varbytes, Varuint};
+use arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+
+impl<'a, T> Arbitrary<'a> for Varuint<T>
+where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(T::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Varbytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(Vec::<u8>::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<u8>::size_hint(depth)
+    }
+}
+
+/// The base is chosen from every [`Base`] in [`BaseIter`], giving the
+/// [`BaseEncoder`] a chance to overrule it. Note that values with the
+/// [`Base::Identity`] base can only be displayed if their bytes are UTF-8.
+impl<'a, T, Enc> Arbitrary<'a> for BaseEncoded<T, Enc>
+where
+    T: Arbitrary<'a> + EncodingInfo,
+    Enc: BaseEncoder,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bases: Vec<Base> = BaseIter::new().collect();
+        let base = *u.choose(&bases)?;
+        Ok(Self::new(Enc::preferred_encoding(base), T::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(u32::size_hint(depth), T::size_hint(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Base58Encoder, EncodedVarbytes};
+
+    #[test]
+    fn test_arbitrary_varuint() {
+        let mut u = Unstructured::new(&[0x2a, 0, 0, 0, 0, 0, 0, 0]);
+        let v = Varuint::<u64>::arbitrary(&mut u).unwrap();
+        assert_eq!(Varuint(42_u64), v);
+    }
+
+    #[test]
+    fn test_arbitrary_varbytes() {
+        let data = [0xaa; 64];
+        let mut u = Unstructured::new(&data);
+        let v = Varbytes::arbitrary(&mut u).unwrap();
+        assert!(v.iter().all(|b| *b == 0xaa));
+    }
+
+    #[test]
+    fn test_arbitrary_base_encoded() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&data);
+        while let Ok(v) = EncodedVarbytes::arbitrary(&mut u) {
+            if v.encoding() != Base::Identity {
+                assert_eq!(
+                    v,
+                    EncodedVarbytes::try_from(v.to_string().as_str()).unwrap()
+                );
+            }
+            if u.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_base_encoded_overruled() {
+        let data = [7; 32];
+        let mut u = Unstructured::new(&data);
+        let v = BaseEncoded::<Varbytes, Base58Encoder>::arbitrary(&mut u).unwrap();
+        assert_eq!(Base::Base58Btc, v.encoding());
+    }
+}
@@ -1,6 +1,9 @@
 // SPDX-License-Idnetifier: Apache-2.0
 use crate::{
-    error::BaseEncodedError, prelude::Base, BaseEncoder, EncodingInfo, Error, MultibaseEncoder,
+    error::{BaseEncodedError, BaseEncoderError},
+    prelude::Base,
+    BaseEncoder, BaseMarker, DecodeOptions, DecodeReport, EncodedLen, EncodingInfo, Error,
+    FixedEncoder, MultibaseEncoder, Normalization,
 };
 use core::{
     cmp::Ordering,
@@ -9,6 +12,11 @@ use core::{
     marker::PhantomData,
     ops,
 };
+use std::{error::Error as StdError, sync::Arc};
+
+/// type alias for a BaseEncoded that is always encoded with the base selected
+/// by the marker type B, e.g. `FixedBaseEncoded<T, Base58Btc>`
+pub type FixedBaseEncoded<T, B> = BaseEncoded<T, FixedEncoder<B>>;
 
 /// Smart pointer for multibase encoded data. This supports encoding to and
 /// decoding from multibase encoding strings using [`TryFrom<&str>`] and
@@ -32,7 +40,12 @@ where
     /// Construct a new BaseEncoded instance with the given base
     pub fn new(base: Base, t: T) -> Self {
         Self {
-            base,
+            // a fixed BaseEncoder overrules the base
+            base: if Enc::FIXED {
+                Enc::preferred_encoding(base)
+            } else {
+                base
+            },
             t,
             enc: PhantomData,
         }
@@ -61,6 +74,22 @@ where
     }
 }
 
+impl<T, Enc> EncodedLen for BaseEncoded<T, Enc>
+where
+    T: EncodingInfo + EncodedLen,
+    Enc: BaseEncoder,
+{
+    /// Return the length of the binary form of the contained object
+    fn encoded_len(&self) -> usize {
+        self.t.encoded_len()
+    }
+
+    /// Encode the binary form of the contained object into the slice
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        self.t.encode_to_slice(buf)
+    }
+}
+
 impl<T, Enc> From<T> for BaseEncoded<T, Enc>
 where
     T: EncodingInfo,
@@ -72,9 +101,40 @@ where
     }
 }
 
+impl<T, B> From<FixedBaseEncoded<T, B>> for BaseEncoded<T>
+where
+    T: EncodingInfo,
+    B: BaseMarker,
+{
+    fn from(fbe: FixedBaseEncoded<T, B>) -> Self {
+        Self::new(B::BASE, fbe.t)
+    }
+}
+
+impl<T, B> TryFrom<BaseEncoded<T>> for FixedBaseEncoded<T, B>
+where
+    T: EncodingInfo,
+    B: BaseMarker,
+{
+    type Error = Error;
+
+    /// fails if the value is not encoded with B
+    fn try_from(be: BaseEncoded<T>) -> Result<Self, Self::Error> {
+        if be.base != B::BASE {
+            return Err(BaseEncoderError::UnexpectedBase {
+                expected: B::BASE,
+                found: be.base,
+            }
+            .into());
+        }
+        Ok(Self::new(B::BASE, be.t))
+    }
+}
+
 impl<T, Enc> TryFrom<&str> for BaseEncoded<T, Enc>
 where
     T: EncodingInfo + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
     Enc: BaseEncoder,
 {
     type Error = Error;
@@ -82,22 +142,69 @@ where
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match Enc::from_base_encoded(s) {
             Ok(decodings) => {
+                let mut attempts = Vec::default();
                 for (base, v) in decodings {
-                    if let Ok(t) = T::try_from(v.as_slice()) {
-                        return Ok(Self {
+                    match T::try_from(v.as_slice()) {
+                        Ok(t) => {
+                            return Ok(Self {
+                                base,
+                                t,
+                                enc: PhantomData,
+                            })
+                        }
+                        Err(e) => attempts.push(BaseEncodedError::ValueFailed {
                             base,
-                            t,
-                            enc: PhantomData,
-                        })
+                            source: Arc::new(e),
+                        }),
                     }
                 }
-                Err(BaseEncodedError::ValueFailed.into())
+                if attempts.len() == 1 {
+                    Err(attempts.remove(0).into())
+                } else {
+                    Err(BaseEncodedError::AllValuesFailed { attempts }.into())
+                }
             }
             Err(e) => Err(e),
         }
     }
 }
 
+impl<T, Enc> BaseEncoded<T, Enc>
+where
+    T: EncodingInfo + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+    Enc: BaseEncoder,
+{
+    /// Decode a string after normalizing it with the given options, returning
+    /// the normalization that was applied
+    pub fn try_decode_with(
+        s: &str,
+        options: &DecodeOptions,
+    ) -> Result<(Self, Normalization), Error> {
+        let (s, n) = options.normalize::<Enc>(s);
+        Ok((Self::try_from(s.as_ref())?, n))
+    }
+}
+
+impl<T, Enc> BaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone + Into<Vec<u8>> + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+    Enc: BaseEncoder,
+{
+    /// Decode a string, returning a report of how it was decoded
+    pub fn try_decode_report(s: &str) -> Result<(Self, DecodeReport), Error> {
+        let v = Self::try_from(s)?;
+        let report = DecodeReport {
+            original: s.to_string(),
+            base: v.base,
+            prefixed: Enc::decoded_prefixed(s, v.base),
+            identical: v.to_string() == s,
+        };
+        Ok((v, report))
+    }
+}
+
 impl<T, Enc> PartialEq<BaseEncoded<T, Enc>> for BaseEncoded<T, Enc>
 where
     T: EncodingInfo + PartialEq<T> + ?Sized,
@@ -170,17 +277,49 @@ where
     }
 }
 
+/// the number of symbols kept on each side of an abbreviated encoding
+const ABBREVIATED_LEN: usize = 3;
+
 impl<T, Enc> fmt::Display for BaseEncoded<T, Enc>
 where
     T: EncodingInfo + Clone + Into<Vec<u8>>,
     Enc: BaseEncoder,
 {
+    /// `{}` prints the full encoding and a precision truncates it to that many
+    /// characters. `{:#}` abbreviates it to the prefix, the first and last
+    /// three symbols and an ellipsis (e.g. `zQm3…x7Y`). a precision sets the
+    /// number of symbols kept on each side instead (e.g. `{:#.6}`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = Enc::to_base_encoded(self.base, &self.t.clone().into());
+        if !f.alternate() {
+            return f.pad(&s);
+        }
+        let n = f.precision().unwrap_or(ABBREVIATED_LEN);
+        let prefix = Enc::encoded_prefix(self.base).chars().count();
+        let len = s.chars().count();
+        if len <= prefix + 2 * n + 1 {
+            return f.write_str(&s);
+        }
+        let index = |c: usize| s.char_indices().nth(c).map_or(s.len(), |(i, _)| i);
+        write!(f, "{}…{}", &s[..index(prefix + n)], &s[index(len - n)..])
+    }
+}
+
+impl<T, Enc> fmt::LowerHex for BaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    /// `{:x}` prints the inner bytes as lowercase hex regardless of the base,
+    /// `{:#x}` adds a 0x prefix and a precision truncates
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            Enc::to_base_encoded(self.base, &self.t.clone().into())
-        )
+        let mut s = if f.alternate() {
+            "0x".to_string()
+        } else {
+            String::default()
+        };
+        s.push_str(&Base::Base16Lower.encode(self.t.clone().into()));
+        f.pad(&s)
     }
 }
 
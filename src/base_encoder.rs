@@ -1,14 +1,26 @@
 // SPDX-License-Idnetifier: Apache-2.0
 use crate::{
+    base_marker::BaseMarker,
     base_name,
+    base_util::{base_alphabet, first_invalid_char},
     BaseIter,
-    error::{BaseEncodedError, BaseEncoderError},
+    error::BaseEncoderError,
     prelude::Base,
     Error,
 };
+#[cfg(feature = "bech32")]
+use bech32::{FromBase32, ToBase32, Variant};
+use core::marker::PhantomData;
+#[cfg(feature = "base58check")]
+use sha2::{Digest, Sha256};
 
 /// a trait for base encoding implementations
 pub trait BaseEncoder {
+    /// true if the encoder only allows the base returned by
+    /// preferred_encoding, so any other base is overruled when constructing
+    /// and rejected when deserializing
+    const FIXED: bool = false;
+
     /// convert a &[u8] to a base encoded value
     fn to_base_encoded(base: Base, b: &[u8]) -> String;
 
@@ -20,6 +32,52 @@ pub trait BaseEncoder {
 
     /// get the preferred base encoding for this encoder
     fn preferred_encoding(base: Base) -> Base;
+
+    /// get the prefix the encoder puts in front of the data, which by default
+    /// is what it produces when encoding no data at all
+    fn encoded_prefix(base: Base) -> String {
+        Self::to_base_encoded(base, &[])
+    }
+
+    /// get the symbols the encoder uses after the prefix, if it uses a fixed
+    /// alphabet
+    fn alphabet(base: Base) -> Option<&'static str> {
+        base_alphabet(base)
+    }
+
+    /// check if s was decoded with base including its prefix, as opposed to a
+    /// bare decoding
+    fn decoded_prefixed(s: &str, base: Base) -> bool {
+        let prefix = Self::encoded_prefix(base);
+        !prefix.is_empty() && s.starts_with(&prefix)
+    }
+}
+
+/// decode a bare string with the given base, locating the first invalid symbol
+/// on failure. the offset is shifted by skip to account for a prefix.
+fn decode_bare(
+    base: Base,
+    s: &str,
+    strict: bool,
+    skip: usize,
+) -> Result<Vec<u8>, BaseEncoderError> {
+    base.decode(s, strict)
+        .map_err(|source| BaseEncoderError::DecodeFailed {
+            base,
+            offset: first_invalid_char(base, s, !strict).map(|o| o + skip),
+            source,
+        })
+}
+
+/// decode a multibase prefixed string
+fn decode_prefixed(s: &str, strict: bool) -> Result<(Base, Vec<u8>), BaseEncoderError> {
+    let code = s
+        .chars()
+        .next()
+        .ok_or(multibase::Error::InvalidBaseString)?;
+    let base = Base::from_code(code)?;
+    let data = decode_bare(base, &s[code.len_utf8()..], strict, 1)?;
+    Ok((base, data))
 }
 
 /// a multibase encoder implementation for use as the default encoder
@@ -32,7 +90,7 @@ impl BaseEncoder for MultibaseEncoder {
     }
     fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
         // try permissive multibase decoding
-        Ok(vec![multibase::decode(s, false).map_err(|_| BaseEncodedError::ValueFailed)?])
+        Ok(vec![decode_prefixed(s, false)?])
     }
     fn debug_string(base: Base) -> String {
         format!("{} ('{}')", base_name(base), base.code())
@@ -52,10 +110,8 @@ impl BaseEncoder for Base58Encoder {
     }
     fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
         // try strict Base58Btc decoding
-        match Base::Base58Btc.decode(s, true) {
-            Ok(v) => Ok(vec![(Base::Base58Btc, v)]),
-            Err(e) => Err(BaseEncoderError::Base58(format!("{:?}", e)).into()),
-        }
+        let data = decode_bare(Base::Base58Btc, s, true, 0)?;
+        Ok(vec![(Base::Base58Btc, data)])
     }
     fn debug_string(_base: Base) -> String {
         format!("{} ('{}')", base_name(Base::Base58Btc), Base::Base58Btc.code())
@@ -65,6 +121,224 @@ impl BaseEncoder for Base58Encoder {
     }
 }
 
+/// a bare encoder for the base selected by the marker type B, e.g.
+/// `BareEncoder<Base64Url>` for JWTs or `BareEncoder<Base32Lower>` for DNS
+/// labels. encoding omits the multibase prefix and decoding is strict.
+/// [`Base58Encoder`] encodes and decodes the same strings as
+/// `BareEncoder<Base58Btc>` but, unlike it, keeps the base passed to
+/// [`crate::BaseEncoded::new`] and accepts any base tag in compact serde.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BareEncoder<B: BaseMarker>(PhantomData<B>);
+
+impl<B: BaseMarker> BaseEncoder for BareEncoder<B> {
+    const FIXED: bool = true;
+
+    fn to_base_encoded(_base: Base, b: &[u8]) -> String {
+        B::BASE.encode(b)
+    }
+    fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
+        // bare encodings have to be strict decodings to avoid confusion
+        let data = decode_bare(B::BASE, s, true, 0)?;
+        Ok(vec![(B::BASE, data)])
+    }
+    fn debug_string(_base: Base) -> String {
+        format!("{} ('{}')", base_name(B::BASE), B::BASE.code())
+    }
+    fn preferred_encoding(_base: Base) -> Base {
+        B::BASE
+    }
+}
+
+/// the number of checksum bytes appended by the [`Base58CheckEncoder`]
+#[cfg(feature = "base58check")]
+const BASE58CHECK_LEN: usize = 4;
+
+/// the first four bytes of the double SHA-256 of the data
+#[cfg(feature = "base58check")]
+fn base58check_checksum(b: &[u8]) -> [u8; BASE58CHECK_LEN] {
+    let hash = Sha256::digest(Sha256::digest(b));
+    let mut checksum = [0u8; BASE58CHECK_LEN];
+    checksum.copy_from_slice(&hash[..BASE58CHECK_LEN]);
+    checksum
+}
+
+/// a bare Base58Btc encoder that appends a four byte double SHA-256 checksum
+/// to the data, as used by Bitcoin addresses and WIF keys. decoding is strict
+/// and fails with [`BaseEncoderError::InvalidChecksum`] if the checksum does
+/// not match.
+#[cfg(feature = "base58check")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Base58CheckEncoder {}
+
+#[cfg(feature = "base58check")]
+impl BaseEncoder for Base58CheckEncoder {
+    fn to_base_encoded(_base: Base, b: &[u8]) -> String {
+        let mut v = b.to_vec();
+        v.extend_from_slice(&base58check_checksum(b));
+        Base::Base58Btc.encode(v)
+    }
+    fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
+        let mut data = decode_bare(Base::Base58Btc, s, true, 0)?;
+        if data.len() < BASE58CHECK_LEN {
+            return Err(BaseEncoderError::InvalidChecksum {
+                encoding: "Base58Check",
+            }
+            .into());
+        }
+        let checksum = data.split_off(data.len() - BASE58CHECK_LEN);
+        if checksum != base58check_checksum(&data) {
+            return Err(BaseEncoderError::InvalidChecksum {
+                encoding: "Base58Check",
+            }
+            .into());
+        }
+        Ok(vec![(Base::Base58Btc, data)])
+    }
+    fn debug_string(_base: Base) -> String {
+        format!("Base58Check ('{}')", Base::Base58Btc.code())
+    }
+    fn preferred_encoding(_base: Base) -> Base {
+        Base::Base58Btc
+    }
+    fn encoded_prefix(_base: Base) -> String {
+        String::default()
+    }
+}
+
+/// the human-readable part of the strings encoded by a [`Bech32Encoder`]
+#[cfg(feature = "bech32")]
+pub trait Bech32Hrp {
+    /// the human-readable part, e.g. "bc". it must be 1 to 83 ASCII
+    /// characters from '!' to '~' with no uppercase letters, otherwise using
+    /// the [`Bech32Encoder`] fails to compile.
+    const HRP: &'static str;
+}
+
+/// check that hrp is a lowercase bech32 human-readable part
+#[cfg(feature = "bech32")]
+pub(crate) const fn valid_hrp(hrp: &str) -> bool {
+    let b = hrp.as_bytes();
+    if b.is_empty() || b.len() > 83 {
+        return false;
+    }
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] < b'!' || b[i] > b'~' || b[i].is_ascii_uppercase() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// the bech32 symbols
+#[cfg(feature = "bech32")]
+const BECH32_ALPHABET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// the BIP-173 maximum length of a bech32 string
+#[cfg(feature = "bech32")]
+const BECH32_MAX_LEN: usize = 90;
+
+/// a BIP-173 bech32 encoder with the human-readable part selected by the type
+/// H. encoding produces lowercase strings and decoding accepts all-uppercase
+/// strings. decoding fails with [`BaseEncoderError::InvalidChecksum`] if the
+/// checksum does not match, with [`BaseEncoderError::UnexpectedHrp`] if the
+/// human-readable part is not H's and with [`BaseEncoderError::Bech32`] if the
+/// string is longer than the BIP-173 limit of 90 characters. the limit leaves
+/// room for `(83 - H::HRP.len()) * 5 / 8` bytes of data, e.g. 50 bytes for
+/// "bc", and encoding more data produces strings that fail to decode.
+#[cfg(feature = "bech32")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bech32Encoder<H: Bech32Hrp>(PhantomData<H>);
+
+#[cfg(feature = "bech32")]
+impl<H: Bech32Hrp> Bech32Encoder<H> {
+    /// evaluated when the encoder is used so an invalid H::HRP fails to compile
+    const VALID_HRP: () = assert!(valid_hrp(H::HRP), "invalid bech32 human-readable part");
+}
+
+#[cfg(feature = "bech32")]
+impl<H: Bech32Hrp> BaseEncoder for Bech32Encoder<H> {
+    fn to_base_encoded(_base: Base, b: &[u8]) -> String {
+        let () = Self::VALID_HRP;
+        // the hrp is checked at compile time so encoding can't fail
+        bech32::encode(H::HRP, b.to_base32(), Variant::Bech32).unwrap_or_default()
+    }
+    fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
+        let () = Self::VALID_HRP;
+        if s.len() > BECH32_MAX_LEN {
+            return Err(BaseEncoderError::Bech32(format!(
+                "string is longer than {} characters",
+                BECH32_MAX_LEN
+            ))
+            .into());
+        }
+        let invalid_checksum = || BaseEncoderError::InvalidChecksum { encoding: "Bech32" };
+        let (hrp, data, variant) = bech32::decode(s).map_err(|e| match e {
+            bech32::Error::InvalidChecksum => invalid_checksum(),
+            e => BaseEncoderError::Bech32(e.to_string()),
+        })?;
+        // a bech32m checksum is not a valid bech32 checksum
+        if variant != Variant::Bech32 {
+            return Err(invalid_checksum().into());
+        }
+        if hrp != H::HRP {
+            return Err(BaseEncoderError::UnexpectedHrp {
+                expected: H::HRP.to_string(),
+                found: hrp,
+            }
+            .into());
+        }
+        let data =
+            Vec::<u8>::from_base32(&data).map_err(|e| BaseEncoderError::Bech32(e.to_string()))?;
+        Ok(vec![(Base::Base32Lower, data)])
+    }
+    fn debug_string(_base: Base) -> String {
+        format!("Bech32 ('{}')", H::HRP)
+    }
+    fn preferred_encoding(_base: Base) -> Base {
+        Base::Base32Lower
+    }
+    fn encoded_prefix(_base: Base) -> String {
+        format!("{}1", H::HRP)
+    }
+    fn alphabet(_base: Base) -> Option<&'static str> {
+        Some(BECH32_ALPHABET)
+    }
+}
+
+/// a multibase encoder pinned to the base selected by the marker type B. it
+/// always encodes with B and rejects strings with any other multibase prefix.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FixedEncoder<B: BaseMarker>(PhantomData<B>);
+
+impl<B: BaseMarker> BaseEncoder for FixedEncoder<B> {
+    const FIXED: bool = true;
+
+    fn to_base_encoded(_base: Base, b: &[u8]) -> String {
+        multibase::encode(B::BASE, b)
+    }
+    fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
+        // reject other bases before trying to decode
+        if let Some(found) = s.chars().next().and_then(|c| Base::from_code(c).ok()) {
+            if found != B::BASE {
+                return Err(BaseEncoderError::UnexpectedBase {
+                    expected: B::BASE,
+                    found,
+                }
+                .into());
+            }
+        }
+        Ok(vec![decode_prefixed(s, false)?])
+    }
+    fn debug_string(_base: Base) -> String {
+        format!("{} ('{}')", base_name(B::BASE), B::BASE.code())
+    }
+    fn preferred_encoding(_base: Base) -> Base {
+        B::BASE
+    }
+}
+
 /// a speculative encoder that tries to detect the correct encoding and decode it
 /// encoding is always done using multibase so this does not support symetric 
 /// decode/encode round trips. this is useful for decoding CIDs that might be 
@@ -78,8 +352,10 @@ impl BaseEncoder for DetectedEncoder {
     }
     fn from_base_encoded(s: &str) -> Result<Vec<(Base, Vec<u8>)>, Error> {
         // first try permissive multibase decoding
-        if let Ok((base, data)) = multibase::decode(s, false) {
-            return Ok(vec![(base, data)]);
+        let mut attempts = Vec::default();
+        match decode_prefixed(s, false) {
+            Ok((base, data)) => return Ok(vec![(base, data)]),
+            Err(e) => attempts.push(e),
         }
         
         // start at the Identity base so we skip it
@@ -89,13 +365,14 @@ impl BaseEncoder for DetectedEncoder {
         // these have to be strict decodings to avoid confusion
         let mut v = Vec::default();
         for encoding in iter {
-            if let Ok(data) = encoding.decode(s, true) {
-                v.push((encoding, data));
+            match decode_bare(encoding, s, true, 0) {
+                Ok(data) => v.push((encoding, data)),
+                Err(e) => attempts.push(e),
             }
         }
         if v.is_empty() {
-            // raise an error
-            Err(BaseEncodedError::ValueFailed.into())
+            // raise an error listing why each base failed
+            Err(BaseEncoderError::DetectionFailed { attempts }.into())
         } else {
             Ok(v)
         }
@@ -106,4 +383,8 @@ impl BaseEncoder for DetectedEncoder {
     fn preferred_encoding(base: Base) -> Base {
         base
     }
+    fn decoded_prefixed(s: &str, base: Base) -> bool {
+        // bare decodings are only tried when multibase decoding fails
+        matches!(decode_prefixed(s, false), Ok((b, _)) if b == base)
+    }
 }
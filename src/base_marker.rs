@@ -0,0 +1,68 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! Zero-sized marker types, one per [`Base`], for selecting a base at compile
+//! time, e.g. `BaseEncoded<T, BareEncoder<Base64Url>>`
+use crate::prelude::Base;
+use core::fmt;
+
+/// a zero-sized type standing in for one [`Base`]
+pub trait BaseMarker: Clone + fmt::Debug + Default + Eq {
+    /// the base this marker stands for
+    const BASE: Base;
+}
+
+macro_rules! base_markers {
+    ($($base:ident),+ $(,)?) => {
+        $(
+            #[doc = concat!("marker for [`Base::", stringify!($base), "`]")]
+            #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+            pub struct $base;
+
+            impl BaseMarker for $base {
+                const BASE: Base = Base::$base;
+            }
+        )+
+
+        #[cfg(test)]
+        pub(crate) fn all_bases() -> Vec<Base> {
+            vec![$(<$base as BaseMarker>::BASE),+]
+        }
+    };
+}
+
+base_markers!(
+    Identity,
+    Base2,
+    Base8,
+    Base10,
+    Base16Lower,
+    Base16Upper,
+    Base32Lower,
+    Base32Upper,
+    Base32PadLower,
+    Base32PadUpper,
+    Base32HexLower,
+    Base32HexUpper,
+    Base32HexPadLower,
+    Base32HexPadUpper,
+    Base32Z,
+    Base36Lower,
+    Base36Upper,
+    Base58Flickr,
+    Base58Btc,
+    Base64,
+    Base64Pad,
+    Base64Url,
+    Base64UrlPad,
+    Base256Emoji,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BaseIter;
+
+    #[test]
+    fn test_every_base_has_a_marker() {
+        assert_eq!(BaseIter::new().collect::<Vec<_>>(), all_bases());
+    }
+}
@@ -0,0 +1,335 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`std::io::Write`] and [`std::io::Read`] adapters that multibase encode and
+//! decode incrementally, for payloads too large to hold as a single string.
+//! only bases that encode fixed size blocks of bytes as fixed size blocks of
+//! symbols can be streamed: base2, base8, base16, base32 and base64 in all of
+//! their variants.
+use crate::{base_util::first_invalid_char, error::BaseEncoderError, prelude::Base, Error};
+use std::io::{self, Read, Write};
+
+/// the number of text bytes read from the inner reader at a time
+const READ_LEN: usize = 8192;
+
+/// the number of bytes and symbols in an encoded block, if the base is block
+/// aligned
+fn block(base: Base) -> Option<(usize, usize)> {
+    use Base::*;
+    match base {
+        Base2 => Some((1, 8)),
+        Base8 => Some((3, 8)),
+        Base16Lower | Base16Upper => Some((1, 2)),
+        Base32Lower | Base32Upper | Base32PadLower | Base32PadUpper | Base32HexLower
+        | Base32HexUpper | Base32HexPadLower | Base32HexPadUpper | Base32Z => Some((5, 8)),
+        Base64 | Base64Pad | Base64Url | Base64UrlPad => Some((3, 4)),
+        _ => None,
+    }
+}
+
+fn not_block_aligned(base: Base) -> Error {
+    BaseEncoderError::NotBlockAligned { base }.into()
+}
+
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// A writer that multibase encodes everything written to it into the inner
+/// writer. [`MultibaseWriter::finish`] must be called to write the final
+/// partial block.
+///
+/// ```
+/// use multiutil::{prelude::Base, MultibaseWriter};
+/// use std::io::Write;
+///
+/// let mut w = MultibaseWriter::new(Base::Base32Lower, Vec::new()).unwrap();
+/// w.write_all(b"hello").unwrap();
+/// w.write_all(b" world").unwrap();
+/// let s = w.finish().unwrap();
+/// assert_eq!(multibase::encode(Base::Base32Lower, b"hello world").as_bytes(), s);
+/// ```
+pub struct MultibaseWriter<W: Write> {
+    inner: W,
+    base: Base,
+    block: usize,
+    pending: Vec<u8>,
+    prefixed: bool,
+}
+
+impl<W: Write> MultibaseWriter<W> {
+    /// create a writer encoding with the given base, which must be block
+    /// aligned
+    pub fn new(base: Base, inner: W) -> Result<Self, Error> {
+        let (block, _) = block(base).ok_or_else(|| not_block_aligned(base))?;
+        Ok(Self {
+            inner,
+            base,
+            block,
+            pending: Vec::with_capacity(block),
+            prefixed: false,
+        })
+    }
+
+    /// the base being encoded with
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// encode the final partial block, with padding if the base uses it, and
+    /// return the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_prefix()?;
+        if !self.pending.is_empty() {
+            self.inner
+                .write_all(self.base.encode(&self.pending).as_bytes())?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn write_prefix(&mut self) -> io::Result<()> {
+        if !self.prefixed {
+            let mut code = [0u8; 4];
+            self.inner
+                .write_all(self.base.code().encode_utf8(&mut code).as_bytes())?;
+            self.prefixed = true;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for MultibaseWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_prefix()?;
+        let mut data = buf;
+
+        // complete a pending partial block first
+        if !self.pending.is_empty() {
+            let n = data.len().min(self.block - self.pending.len());
+            self.pending.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if self.pending.len() < self.block {
+                return Ok(buf.len());
+            }
+            self.inner
+                .write_all(self.base.encode(&self.pending).as_bytes())?;
+            self.pending.clear();
+        }
+
+        // encode all whole blocks directly from the buffer
+        let whole = data.len() - data.len() % self.block;
+        if whole > 0 {
+            self.inner
+                .write_all(self.base.encode(&data[..whole]).as_bytes())?;
+        }
+        self.pending.extend_from_slice(&data[whole..]);
+        Ok(buf.len())
+    }
+
+    /// flush the inner writer. a pending partial block is not written until
+    /// [`MultibaseWriter::finish`] is called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader that decodes the multibase encoded text read from the inner
+/// reader. The multibase prefix is read when the reader is created.
+///
+/// ```
+/// use multiutil::{prelude::Base, MultibaseReader};
+/// use std::io::Read;
+///
+/// let s = multibase::encode(Base::Base64Url, b"hello world");
+/// let mut r = MultibaseReader::new(s.as_bytes()).unwrap();
+/// assert_eq!(Base::Base64Url, r.base());
+/// let mut b = Vec::new();
+/// r.read_to_end(&mut b).unwrap();
+/// assert_eq!(b"hello world".to_vec(), b);
+/// ```
+pub struct MultibaseReader<R: Read> {
+    inner: R,
+    base: Base,
+    block: usize,
+    text: Vec<u8>,
+    decoded: Vec<u8>,
+    pos: usize,
+    offset: usize,
+    eof: bool,
+}
+
+impl<R: Read> MultibaseReader<R> {
+    /// create a reader, reading the multibase prefix to learn the base. it is
+    /// an error if the base is not block aligned.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut code = [0u8; 1];
+        inner.read_exact(&mut code)?;
+        let base = Base::from_code(char::from(code[0]))
+            .map_err(|e| invalid_data(BaseEncoderError::from(e).into()))?;
+        let (_, block) = block(base).ok_or_else(|| invalid_data(not_block_aligned(base)))?;
+        Ok(Self {
+            inner,
+            base,
+            block,
+            text: Vec::with_capacity(READ_LEN + block),
+            decoded: Vec::new(),
+            pos: 0,
+            offset: 1,
+            eof: false,
+        })
+    }
+
+    /// the base being decoded
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// consume self and return the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// read text until at least one block decodes or the input ends
+    fn fill(&mut self) -> io::Result<()> {
+        self.decoded.clear();
+        self.pos = 0;
+        let mut chunk = [0u8; READ_LEN];
+        while self.decoded.is_empty() && !self.eof {
+            let n = match self.inner.read(&mut chunk) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            self.text.extend_from_slice(&chunk[..n]);
+            let len = if n == 0 {
+                self.eof = true;
+                self.text.len()
+            } else {
+                self.text.len() - self.text.len() % self.block
+            };
+            if len > 0 {
+                self.decode(len)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// decode the first len bytes of text
+    fn decode(&mut self, len: usize) -> io::Result<()> {
+        let s = std::str::from_utf8(&self.text[..len]).map_err(|e| {
+            let source = multibase::Error::InvalidBaseString;
+            let e = BaseEncoderError::DecodeFailed {
+                base: self.base,
+                offset: Some(self.offset + e.valid_up_to()),
+                source,
+            };
+            invalid_data(e.into())
+        })?;
+        self.decoded = self.base.decode(s, false).map_err(|source| {
+            let e = BaseEncoderError::DecodeFailed {
+                base: self.base,
+                offset: first_invalid_char(self.base, s, true).map(|o| o + self.offset),
+                source,
+            };
+            invalid_data(e.into())
+        })?;
+        self.offset += s.chars().count();
+        self.text.drain(..len);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for MultibaseReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.decoded.len() {
+            self.fill()?;
+        }
+        let n = buf.len().min(self.decoded.len() - self.pos);
+        buf[..n].copy_from_slice(&self.decoded[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BaseIter;
+
+    /// a reader that returns at most 7 bytes per read
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn data() -> Vec<u8> {
+        (0..20_000_u32).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_write_matches_multibase() {
+        let data = data();
+        for base in BaseIter::new().filter(|b| block(*b).is_some()) {
+            let mut w = MultibaseWriter::new(base, Vec::new()).unwrap();
+            for chunk in data.chunks(13) {
+                w.write_all(chunk).unwrap();
+            }
+            let s = w.finish().unwrap();
+            assert_eq!(multibase::encode(base, &data).as_bytes(), s.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_read_round_trip() {
+        let data = data();
+        for base in BaseIter::new().filter(|b| block(*b).is_some()) {
+            let s = multibase::encode(base, &data);
+            let mut r = MultibaseReader::new(Trickle(s.as_bytes())).unwrap();
+            assert_eq!(base, r.base());
+            let mut b = Vec::new();
+            r.read_to_end(&mut b).unwrap();
+            assert_eq!(data, b);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let s = MultibaseWriter::new(Base::Base16Lower, Vec::new())
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(b"f".to_vec(), s);
+        let mut b = Vec::new();
+        MultibaseReader::new(s.as_slice())
+            .unwrap()
+            .read_to_end(&mut b)
+            .unwrap();
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_not_block_aligned() {
+        assert!(MultibaseWriter::new(Base::Base58Btc, Vec::new()).is_err());
+        assert!(MultibaseReader::new("z2NEpo7TZRRrLZSi2U".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_invalid_char_offset() {
+        let mut s = multibase::encode(Base::Base16Lower, data());
+        s.replace_range(20_001..20_002, "z");
+        let mut r = MultibaseReader::new(s.as_bytes()).unwrap();
+        let e = r.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, e.kind());
+        match e.into_inner().unwrap().downcast::<Error>().map(|e| *e) {
+            Ok(Error::BaseEncoder(BaseEncoderError::DecodeFailed { offset, .. })) => {
+                assert_eq!(Some(20_001), offset)
+            }
+            r => panic!("unexpected error: {:?}", r),
+        }
+    }
+}
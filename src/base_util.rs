@@ -1,5 +1,5 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::prelude::Base;
+use crate::{error::BaseEncoderError, prelude::Base, Case, Error};
 
 /// convert a multibase Base to its string equivalent
 pub fn base_name(b: Base) -> String {
@@ -33,6 +33,145 @@ pub fn base_name(b: Base) -> String {
     .to_string()
 }
 
+/// the name of a multibase Base in the multibase spec's table
+fn base_spec_name(b: Base) -> &'static str {
+    use Base::*;
+    match b {
+        Identity => "identity",
+        Base2 => "base2",
+        Base8 => "base8",
+        Base10 => "base10",
+        Base16Lower => "base16",
+        Base16Upper => "base16upper",
+        Base32Lower => "base32",
+        Base32Upper => "base32upper",
+        Base32PadLower => "base32pad",
+        Base32PadUpper => "base32padupper",
+        Base32HexLower => "base32hex",
+        Base32HexUpper => "base32hexupper",
+        Base32HexPadLower => "base32hexpad",
+        Base32HexPadUpper => "base32hexpadupper",
+        Base32Z => "base32z",
+        Base36Lower => "base36",
+        Base36Upper => "base36upper",
+        Base58Flickr => "base58flickr",
+        Base58Btc => "base58btc",
+        Base64 => "base64",
+        Base64Pad => "base64pad",
+        Base64Url => "base64url",
+        Base64UrlPad => "base64urlpad",
+        Base256Emoji => "base256emoji",
+    }
+}
+
+/// the symbols, including padding, used by a multibase Base. returns None for
+/// bases that accept any symbol or whose alphabet is not ASCII.
+pub(crate) fn base_alphabet(b: Base) -> Option<&'static str> {
+    use Base::*;
+    match b {
+        Identity | Base256Emoji => None,
+        Base2 => Some("01"),
+        Base8 => Some("01234567"),
+        Base10 => Some("0123456789"),
+        Base16Lower => Some("0123456789abcdef"),
+        Base16Upper => Some("0123456789ABCDEF"),
+        Base32Lower => Some("abcdefghijklmnopqrstuvwxyz234567"),
+        Base32Upper => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+        Base32PadLower => Some("abcdefghijklmnopqrstuvwxyz234567="),
+        Base32PadUpper => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567="),
+        Base32HexLower => Some("0123456789abcdefghijklmnopqrstuv"),
+        Base32HexUpper => Some("0123456789ABCDEFGHIJKLMNOPQRSTUV"),
+        Base32HexPadLower => Some("0123456789abcdefghijklmnopqrstuv="),
+        Base32HexPadUpper => Some("0123456789ABCDEFGHIJKLMNOPQRSTUV="),
+        Base32Z => Some("ybndrfg8ejkmcpqxot1uwisza345h769"),
+        Base36Lower => Some("0123456789abcdefghijklmnopqrstuvwxyz"),
+        Base36Upper => Some("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        Base58Flickr => Some("123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ"),
+        Base58Btc => Some("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"),
+        Base64 => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+        Base64Pad => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/="),
+        Base64Url => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"),
+        Base64UrlPad => Some("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_="),
+    }
+}
+
+/// the character offset of the first symbol in s that is not in the base's
+/// alphabet, if there is one. with ignore_case the letters of case-insensitive
+/// bases match in either case, the same as permissive decoding.
+pub(crate) fn first_invalid_char(b: Base, s: &str, ignore_case: bool) -> Option<usize> {
+    let alphabet = base_alphabet(b)?;
+    let fold: fn(&char) -> char = match letter_case(b) {
+        Some(Case::Lower) if ignore_case => char::to_ascii_lowercase,
+        Some(Case::Upper) if ignore_case => char::to_ascii_uppercase,
+        _ => |c: &char| *c,
+    };
+    s.chars().position(|c| !alphabet.contains(fold(&c)))
+}
+
+/// the case of a case-insensitive base
+pub(crate) fn letter_case(b: Base) -> Option<Case> {
+    use Base::*;
+    match b {
+        Base16Lower | Base32Lower | Base32PadLower | Base32HexLower | Base32HexPadLower
+        | Base32Z | Base36Lower => Some(Case::Lower),
+        Base16Upper | Base32Upper | Base32PadUpper | Base32HexUpper | Base32HexPadUpper
+        | Base36Upper => Some(Case::Upper),
+        _ => None,
+    }
+}
+
+/// convert a string to a multibase Base, the inverse of [`base_name`]. this
+/// accepts the names returned by [`base_name`] and the multibase spec names
+/// (e.g. "base58btc", "base32upper") case-insensitively, as well as the
+/// single character multibase codes (e.g. "z"), which are case-sensitive.
+pub fn base_from_name(s: &str) -> Result<Base, Error> {
+    let mut chars = s.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if let Ok(base) = Base::from_code(c) {
+            return Ok(base);
+        }
+    }
+
+    let name = s.to_lowercase();
+    let matches = |b: &Base| base_name(*b).to_lowercase() == name || base_spec_name(*b) == name;
+    if let Some(base) = BaseIter::new().find(matches) {
+        return Ok(base);
+    }
+
+    // find the closest name to suggest for typos
+    let suggestion = BaseIter::new()
+        .flat_map(|b| {
+            [
+                (edit_distance(&name, &base_name(b).to_lowercase()), b),
+                (edit_distance(&name, base_spec_name(b)), b),
+            ]
+        })
+        .min_by_key(|(d, _)| *d)
+        .filter(|(d, _)| *d <= 3)
+        .map(|(_, b)| base_name(b));
+
+    Err(BaseEncoderError::UnknownBaseName {
+        name: s.to_string(),
+        suggestion,
+    }
+    .into())
+}
+
+/// the Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let sub = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = sub.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
 /// Iterator over the Base enum values
 pub struct BaseIter(Option<Base>);
 
@@ -109,6 +248,51 @@ mod tests {
         assert_eq!(iter.next(), None)
     }
 
+    #[test]
+    fn test_base_from_name() {
+        for b in BaseIter::new() {
+            assert_eq!(b, base_from_name(&base_name(b)).unwrap());
+            assert_eq!(b, base_from_name(base_spec_name(b)).unwrap());
+            assert_eq!(b, base_from_name(&b.code().to_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_base_from_name_case_insensitive() {
+        assert_eq!(Base::Base58Btc, base_from_name("BASE58BTC").unwrap());
+        assert_eq!(Base::Base32Upper, base_from_name("base32upper").unwrap());
+        assert_eq!(Base::Base32Lower, base_from_name("Base32").unwrap());
+    }
+
+    #[test]
+    fn test_base_from_name_codes_case_sensitive() {
+        assert_eq!(Base::Base16Lower, base_from_name("f").unwrap());
+        assert_eq!(Base::Base16Upper, base_from_name("F").unwrap());
+    }
+
+    #[test]
+    fn test_base_from_name_suggestion() {
+        let e = base_from_name("base58bct").unwrap_err();
+        assert!(e.to_string().contains("did you mean Base58Btc?"));
+        let e = base_from_name("nothing-like-a-base").unwrap_err();
+        assert!(!e.to_string().contains("did you mean"));
+    }
+
+    #[test]
+    fn test_first_invalid_char() {
+        assert_eq!(None, first_invalid_char(Base::Base16Lower, "42aa", false));
+        assert_eq!(
+            Some(2),
+            first_invalid_char(Base::Base16Lower, "42AA", false)
+        );
+        assert_eq!(Some(0), first_invalid_char(Base::Base58Btc, "0abc", false));
+        assert_eq!(None, first_invalid_char(Base::Identity, "anything", false));
+        // permissive decoding ignores the case of case-insensitive bases
+        assert_eq!(None, first_invalid_char(Base::Base16Lower, "42AA", true));
+        assert_eq!(Some(3), first_invalid_char(Base::Base16Upper, "42aG", true));
+        assert_eq!(Some(1), first_invalid_char(Base::Base58Btc, "1l", true));
+    }
+
     #[test]
     fn test_all_iter() {
         let mut iter = BaseIter::new();
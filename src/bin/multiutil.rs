@@ -0,0 +1,279 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! multiutil command line tool for inspecting and converting multibase strings
+use clap::{Parser, Subcommand};
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::{
+    base_from_name, error::display_chain, prelude::Base, BaseEncoder, DetectedEncoder,
+    MultibaseEncoder, Varuint,
+};
+use std::io::{self, Read, Write};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "multiutil",
+    version,
+    about = "Multibase, varuint and varbytes utility"
+)]
+struct Cli {
+    #[command(subcommand)]
+    cmd: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Multibase encode the bytes read from stdin
+    Encode {
+        /// The base to encode with, by name (e.g. Base58Btc) or code (e.g. z)
+        #[arg(short, long, value_parser = parse_base, default_value = "Base58Btc")]
+        base: Base,
+    },
+    /// Decode a multibase string and print the bytes as hex
+    Decode {
+        /// Write the raw decoded bytes to stdout instead of hex
+        #[arg(short, long)]
+        raw: bool,
+        /// The multibase string, read from stdin if omitted
+        input: Option<String>,
+    },
+    /// List every base that successfully decodes a string
+    Detect {
+        /// The string to detect, read from stdin if omitted
+        input: Option<String>,
+    },
+    /// Re-encode a multibase string in a different base
+    Rebase {
+        /// The base to encode with, by name (e.g. Base58Btc) or code (e.g. z)
+        #[arg(short, long, value_parser = parse_base)]
+        to: Base,
+        /// The multibase string, read from stdin if omitted
+        input: Option<String>,
+    },
+    /// Varuint encoding and decoding
+    #[command(subcommand)]
+    Varuint(VaruintCommand),
+    /// Varbytes stream processing
+    #[command(subcommand)]
+    Varbytes(VarbytesCommand),
+}
+
+#[derive(Subcommand)]
+enum VaruintCommand {
+    /// Encode a decimal number as a hex varuint
+    Encode {
+        /// The number to encode
+        value: u128,
+    },
+    /// Decode a hex varuint to a decimal number
+    Decode {
+        /// The hex encoded varuint
+        hex: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum VarbytesCommand {
+    /// Split a stream of varbytes read from stdin and print each as hex
+    Split,
+}
+
+/// parse a base from its name or its single character multibase code
+fn parse_base(s: &str) -> std::result::Result<Base, String> {
+    base_from_name(s).map_err(|e| e.to_string())
+}
+
+/// return the argument or the trimmed contents of stdin
+fn input_or_stdin(input: Option<String>, stdin: &mut impl Read) -> Result<String> {
+    match input {
+        Some(s) => Ok(s),
+        None => {
+            let mut s = String::new();
+            stdin.read_to_string(&mut s)?;
+            Ok(s.trim().to_string())
+        }
+    }
+}
+
+fn hex(b: &[u8]) -> String {
+    Base::Base16Lower.encode(b)
+}
+
+fn main() {
+    let result = run(
+        Cli::parse(),
+        &mut io::stdin().lock(),
+        &mut io::stdout().lock(),
+    );
+    std::process::exit(report(result, &mut io::stderr()));
+}
+
+/// print the error, if any, with its chain of causes and return the exit code
+fn report(result: Result<()>, stderr: &mut impl Write) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            let _ = writeln!(stderr, "error: {}", display_chain(e.as_ref()));
+            1
+        }
+    }
+}
+
+fn run(cli: Cli, stdin: &mut impl Read, stdout: &mut impl Write) -> Result<()> {
+    match cli.cmd {
+        Command::Encode { base } => {
+            let mut b = Vec::new();
+            stdin.read_to_end(&mut b)?;
+            writeln!(stdout, "{}", MultibaseEncoder::to_base_encoded(base, &b))?;
+        }
+        Command::Decode { raw, input } => {
+            let s = input_or_stdin(input, stdin)?;
+            for (_, b) in MultibaseEncoder::from_base_encoded(&s)? {
+                if raw {
+                    stdout.write_all(&b)?;
+                } else {
+                    writeln!(stdout, "{}", hex(&b))?;
+                }
+            }
+        }
+        Command::Detect { input } => {
+            let s = input_or_stdin(input, stdin)?;
+            for (base, b) in DetectedEncoder::from_base_encoded(&s)? {
+                writeln!(
+                    stdout,
+                    "{}: {} bytes: {}",
+                    DetectedEncoder::debug_string(base),
+                    b.len(),
+                    hex(&b)
+                )?;
+            }
+        }
+        Command::Rebase { to, input } => {
+            let s = input_or_stdin(input, stdin)?;
+            for (_, b) in MultibaseEncoder::from_base_encoded(&s)? {
+                writeln!(stdout, "{}", MultibaseEncoder::to_base_encoded(to, &b))?;
+            }
+        }
+        Command::Varuint(VaruintCommand::Encode { value }) => {
+            writeln!(stdout, "{}", hex(&value.encode_into()))?;
+        }
+        Command::Varuint(VaruintCommand::Decode { hex }) => {
+            let b = Base::Base16Lower.decode(hex.to_lowercase(), true)?;
+            let (v, ptr) = Varuint::<u128>::try_decode_from(&b)?;
+            if !ptr.is_empty() {
+                return Err(format!("{} trailing bytes after varuint", ptr.len()).into());
+            }
+            writeln!(stdout, "{}", v)?;
+        }
+        Command::Varbytes(VarbytesCommand::Split) => {
+            let mut b = Vec::new();
+            stdin.read_to_end(&mut b)?;
+            let mut ptr = b.as_slice();
+            while !ptr.is_empty() {
+                let (len, rest) = Varuint::<usize>::try_decode_from(ptr)?;
+                if rest.len() < *len {
+                    return Err(format!(
+                        "truncated varbytes: needed {} bytes, {} available",
+                        *len,
+                        rest.len()
+                    )
+                    .into());
+                }
+                writeln!(stdout, "{}", hex(&rest[..*len]))?;
+                ptr = &rest[*len..];
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// run the tool with the arguments and stdin, returning the exit code,
+    /// stdout and stderr
+    fn cmd(args: &[&str], stdin: &[u8]) -> (i32, String, String) {
+        let cli =
+            Cli::try_parse_from(std::iter::once("multiutil").chain(args.iter().copied())).unwrap();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let code = report(run(cli, &mut &stdin[..], &mut stdout), &mut stderr);
+        (
+            code,
+            String::from_utf8(stdout).unwrap(),
+            String::from_utf8(stderr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(
+            (0, "f0102\n".into(), "".into()),
+            cmd(&["encode", "-b", "f"], &[1, 2])
+        );
+        assert_eq!(
+            (0, "0102\n".into(), "".into()),
+            cmd(&["decode"], b"f0102\n")
+        );
+        let (code, out, _) = cmd(&["decode", "--raw", "f0102"], &[]);
+        assert_eq!((0, "\u{1}\u{2}".to_string()), (code, out));
+    }
+
+    #[test]
+    fn test_rebase() {
+        assert_eq!(
+            (0, "z5T\n".into(), "".into()),
+            cmd(&["rebase", "--to", "Base58Btc", "f0102"], &[])
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let (code, out, err) = cmd(&["detect", "f0102"], &[]);
+        assert_eq!(0, code);
+        assert_eq!("Base16Lower ('f'): 2 bytes: 0102\n", out);
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn test_detect_failed() {
+        let (code, out, err) = cmd(&["detect", "!!"], &[]);
+        assert_eq!(1, code);
+        assert!(out.is_empty());
+        assert!(err.starts_with("error: No base could decode the data: "));
+        // each cause is printed once
+        let cause = "Failed to decode Base2 data";
+        assert_eq!(1, err.matches(cause).count());
+        assert_eq!(1, err.lines().count());
+    }
+
+    #[test]
+    fn test_varuint() {
+        assert_eq!(
+            (0, "ed01\n".into(), "".into()),
+            cmd(&["varuint", "encode", "237"], &[])
+        );
+        assert_eq!(
+            (0, "237\n".into(), "".into()),
+            cmd(&["varuint", "decode", "ED01"], &[])
+        );
+        let (code, _, err) = cmd(&["varuint", "decode", "ed0100"], &[]);
+        assert_eq!(1, code);
+        assert_eq!("error: 1 trailing bytes after varuint\n", err);
+    }
+
+    #[test]
+    fn test_varbytes_split() {
+        let (code, out, _) = cmd(&["varbytes", "split"], &[2, 1, 2, 0, 1, 3]);
+        assert_eq!((0, "0102\n\n03\n"), (code, out.as_str()));
+        let (code, out, err) = cmd(&["varbytes", "split"], &[1, 1, 3]);
+        assert_eq!(1, code);
+        assert_eq!("01\n", out);
+        assert_eq!(
+            "error: truncated varbytes: needed 3 bytes, 0 available\n",
+            err
+        );
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! Extension traits for reading and writing [`crate::Varuint`] and
+//! [`crate::Varbytes`] values with the bytes crate's [`Buf`] and [`BufMut`]
+use crate::{error::VarbytesError, EncodedLen, Error, Varbytes, Varuint, VaruintRepr};
+use bytes::{Buf, BufMut, Bytes};
+use multitrait::TryDecodeFrom;
+
+/// the longest possible varuint encoding, that of u128::MAX
+const MAX_VARUINT_LEN: usize = 19;
+
+/// Extension trait for decoding varuint and varbytes values from a [`Buf`]
+pub trait VarBuf: Buf {
+    /// read a varuint encoded value and advance past it. if the buffer ends
+    /// before the varuint does, the bytes read so far are consumed.
+    fn get_varuint<T>(&mut self) -> Result<T, Error>
+    where
+        T: VaruintRepr,
+    {
+        let mut b = [0u8; MAX_VARUINT_LEN];
+        let mut len = 0;
+        while self.has_remaining() && len < MAX_VARUINT_LEN {
+            b[len] = self.get_u8();
+            len += 1;
+            if b[len - 1] & 0x80 == 0 {
+                break;
+            }
+        }
+        let (vu, _) = Varuint::<T>::try_decode_from(&b[..len])?;
+        Ok(vu.0)
+    }
+
+    /// read a varuint length prefix followed by that many bytes. when the
+    /// buffer is a [`Bytes`] the returned value shares its memory.
+    fn get_varbytes(&mut self) -> Result<Bytes, Error> {
+        let len = self.get_varuint::<usize>()?;
+        if self.remaining() < len {
+            return Err(VarbytesError::Truncated {
+                needed: len,
+                available: self.remaining(),
+            }
+            .into());
+        }
+        Ok(self.copy_to_bytes(len))
+    }
+}
+
+impl<B: Buf + ?Sized> VarBuf for B {}
+
+/// Extension trait for encoding varuint and varbytes values into a [`BufMut`]
+pub trait VarBufMut: BufMut {
+    /// write the value as a varuint
+    fn put_varuint<T>(&mut self, t: T)
+    where
+        Varuint<T>: EncodedLen,
+    {
+        let mut b = [0u8; MAX_VARUINT_LEN];
+        // the buffer is large enough for any varuint so this cannot fail
+        if let Ok(len) = Varuint(t).encode_to_slice(&mut b) {
+            self.put_slice(&b[..len]);
+        }
+    }
+
+    /// write the slice as a varuint length prefix followed by the bytes
+    fn put_varbytes(&mut self, v: &[u8]) {
+        self.put_varuint(v.len());
+        self.put_slice(v);
+    }
+}
+
+impl<B: BufMut + ?Sized> VarBufMut for B {}
+
+/// Wraps the [`Bytes`] as the inner value without decoding a length prefix
+impl From<Bytes> for Varbytes {
+    fn from(b: Bytes) -> Self {
+        Self(b.into())
+    }
+}
+
+/// Converts the inner value to [`Bytes`] without a length prefix
+impl From<Varbytes> for Bytes {
+    fn from(vb: Varbytes) -> Self {
+        Bytes::from(vb.to_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use multitrait::EncodeInto;
+
+    #[test]
+    fn test_varuint_round_trip() {
+        let mut b = BytesMut::new();
+        b.put_varuint(0x3fff_u64);
+        b.put_varuint(u128::MAX);
+        assert_eq!(0x3fff_u64.encode_into().as_slice(), &b[..2]);
+        let mut b = b.freeze();
+        assert_eq!(0x3fff_u64, b.get_varuint::<u64>().unwrap());
+        assert_eq!(u128::MAX, b.get_varuint::<u128>().unwrap());
+        assert!(!b.has_remaining());
+    }
+
+    #[test]
+    fn test_varbytes_round_trip() {
+        let mut b = BytesMut::new();
+        b.put_varbytes(&[1, 2, 3]);
+        assert_eq!(Varbytes(vec![1, 2, 3]).encode_into().as_slice(), &b[..]);
+        let mut b = b.freeze();
+        assert_eq!(Bytes::from_static(&[1, 2, 3]), b.get_varbytes().unwrap());
+    }
+
+    #[test]
+    fn test_get_varbytes_truncated() {
+        let mut b = Bytes::from_static(&[4, 1, 2, 3]);
+        assert!(b.get_varbytes().is_err());
+    }
+
+    #[test]
+    fn test_get_varuint_empty() {
+        let mut b = Bytes::new();
+        assert!(b.get_varuint::<u64>().is_err());
+    }
+
+    #[test]
+    fn test_bytes_conversion() {
+        let vb: Varbytes = Bytes::from_static(&[1, 2, 3]).into();
+        assert_eq!(Varbytes(vec![1, 2, 3]), vb);
+        let b: Bytes = vb.into();
+        assert_eq!(Bytes::from_static(&[1, 2, 3]), b);
+    }
+}
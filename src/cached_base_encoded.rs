@@ -0,0 +1,322 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{BaseEncoded, BaseEncoder, EncodingInfo, Error, MultibaseEncoder};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops,
+};
+use multibase::Base;
+use std::{error::Error as StdError, sync::OnceLock};
+
+/// A [`BaseEncoded`] that memoizes its encoded string so that repeated
+/// [`fmt::Display`] and [`Hash`] calls only encode once. Mutable access to the
+/// inner value through [`ops::DerefMut`] drops the memoized string. A value
+/// decoded with [`TryFrom<&str>`] keeps the string it was decoded from and
+/// displays it verbatim, even if re-encoding would produce different text.
+///
+/// ```
+/// use multiutil::{prelude::*, CachedBaseEncoded};
+///
+/// // a bare base2 string is re-encoded with the multibase prefix
+/// let s = "0000000100000001";
+/// let v = CachedBaseEncoded::<Varbytes, DetectedEncoder>::try_from(s).unwrap();
+/// assert_eq!(s, v.to_string());
+/// assert_eq!("00000000100000001", v.encoded());
+/// ```
+pub struct CachedBaseEncoded<T, Enc = MultibaseEncoder>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    inner: BaseEncoded<T, Enc>,
+    encoded: OnceLock<String>,
+    original: Option<String>,
+}
+
+impl<T, Enc> CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    /// Construct a new CachedBaseEncoded instance with the given base
+    pub fn new(base: Base, t: T) -> Self {
+        BaseEncoded::new(base, t).into()
+    }
+
+    /// the string this was decoded from, if it was decoded and has not been
+    /// mutated since
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+
+    /// Convert to the uncached BaseEncoded, consuming self
+    pub fn to_base_encoded(self) -> BaseEncoded<T, Enc> {
+        self.inner
+    }
+
+    /// Convert to the inner T type, consuming self
+    pub fn to_inner(self) -> T {
+        self.inner.to_inner()
+    }
+}
+
+impl<T, Enc> CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    /// the encoding of the inner value, encoded on first use
+    pub fn encoded(&self) -> &str {
+        self.encoded.get_or_init(|| self.inner.to_string())
+    }
+
+    /// the original string if there is one, otherwise the encoding
+    pub fn as_str(&self) -> &str {
+        self.original().unwrap_or_else(|| self.encoded())
+    }
+}
+
+impl<T, Enc> EncodingInfo for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    fn preferred_encoding() -> Base {
+        BaseEncoded::<T, Enc>::preferred_encoding()
+    }
+
+    fn encoding(&self) -> Base {
+        self.inner.encoding()
+    }
+}
+
+impl<T, Enc> From<BaseEncoded<T, Enc>> for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    fn from(inner: BaseEncoded<T, Enc>) -> Self {
+        Self {
+            inner,
+            encoded: OnceLock::new(),
+            original: None,
+        }
+    }
+}
+
+impl<T, Enc> From<T> for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    fn from(t: T) -> Self {
+        BaseEncoded::from(t).into()
+    }
+}
+
+impl<T, Enc> From<CachedBaseEncoded<T, Enc>> for BaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    fn from(cbe: CachedBaseEncoded<T, Enc>) -> Self {
+        cbe.inner
+    }
+}
+
+impl<T, Enc> TryFrom<&str> for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+    Enc: BaseEncoder,
+{
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Ok(Self {
+            inner: BaseEncoded::try_from(s)?,
+            encoded: OnceLock::new(),
+            original: Some(s.to_string()),
+        })
+    }
+}
+
+impl<T, Enc> Clone for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone,
+    Enc: BaseEncoder + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            encoded: self.encoded.clone(),
+            original: self.original.clone(),
+        }
+    }
+}
+
+impl<T, Enc> PartialEq for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + PartialEq,
+    Enc: BaseEncoder,
+{
+    /// equal if the inner values are equal, regardless of the original strings
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, Enc> Eq for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Eq,
+    Enc: BaseEncoder,
+{
+}
+
+impl<T, Enc> PartialOrd for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + PartialOrd,
+    Enc: BaseEncoder,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<T, Enc> Ord for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Ord,
+    Enc: BaseEncoder,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<T, Enc> Hash for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    /// hashes the encoding, not the original string, to agree with [`Eq`]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.encoded().hash(state);
+    }
+}
+
+impl<T, Enc> ops::Deref for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T, Enc> ops::DerefMut for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    /// drops the memoized and original strings since the value may change
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.encoded = OnceLock::new();
+        self.original = None;
+        &mut self.inner
+    }
+}
+
+impl<T, Enc> fmt::Display for CachedBaseEncoded<T, Enc>
+where
+    T: EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    /// `{}` prints the original string or the memoized encoding. `{:#}`
+    /// abbreviates the encoding the same as [`BaseEncoded`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            fmt::Display::fmt(&self.inner, f)
+        } else {
+            f.pad(self.as_str())
+        }
+    }
+}
+
+impl<T, Enc> fmt::Debug for CachedBaseEncoded<T, Enc>
+where
+    T: fmt::Debug + EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DetectedEncoder, Varbytes};
+    use std::collections::hash_map::DefaultHasher;
+
+    type CachedVarbytes = CachedBaseEncoded<Varbytes>;
+
+    fn hash<H: Hash>(h: &H) -> u64 {
+        let mut state = DefaultHasher::new();
+        h.hash(&mut state);
+        state.finish()
+    }
+
+    #[test]
+    fn test_memoized() {
+        let v = CachedVarbytes::new(Base::Base16Lower, Varbytes(vec![1, 2]));
+        assert_eq!(None, v.original());
+        assert_eq!("f020102", v.to_string());
+        // the same allocation is returned each time
+        assert_eq!(v.encoded().as_ptr(), v.encoded().as_ptr());
+        assert_eq!(v.encoded().as_ptr(), v.as_str().as_ptr());
+    }
+
+    #[test]
+    fn test_original_preserved() {
+        let v = CachedVarbytes::try_from("BAEAQ").unwrap();
+        assert_eq!(Some("BAEAQ"), v.original());
+        assert_eq!("BAEAQ", v.to_string());
+        assert_eq!("BAEAQ", v.encoded());
+        assert_eq!(Base::Base32Upper, v.encoding());
+    }
+
+    #[test]
+    fn test_deref_mut_invalidates() {
+        let mut v = CachedVarbytes::try_from("f0101").unwrap();
+        assert_eq!("f0101", v.encoded());
+        v.0.push(2);
+        assert_eq!(None, v.original());
+        assert_eq!("f020102", v.to_string());
+    }
+
+    #[test]
+    fn test_matches_base_encoded() {
+        let be = Varbytes::encoded_new(Base::Base58Btc, vec![1, 2, 3]);
+        let v = CachedVarbytes::from(be.clone());
+        assert_eq!(be.to_string(), v.to_string());
+        assert_eq!(format!("{:#}", be), format!("{:#}", v));
+        assert_eq!(format!("{:?}", be), format!("{:?}", v));
+        assert_eq!(hash(&be), hash(&v));
+        assert_eq!(be, BaseEncoded::from(v));
+    }
+
+    #[test]
+    fn test_eq_and_hash_ignore_original() {
+        type Detected = CachedBaseEncoded<Varbytes, DetectedEncoder>;
+        let v1 = Detected::try_from("0000000100000001").unwrap();
+        let v2 = Detected::try_from("00000000100000001").unwrap();
+        assert_ne!(v1.to_string(), v2.to_string());
+        assert_eq!(v1, v2);
+        assert_eq!(hash(&v1), hash(&v2));
+    }
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    decode_usize, encoded_len::check_len, error::CodecPayloadError, prelude::Codec, BaseEncoded,
+    CodecInfo, EncodedLen, EncodingInfo, Error, Varuint,
+};
+use multibase::Base;
+use multitrait::prelude::{EncodeInto, TryDecodeFrom};
+use std::{error::Error as StdError, sync::Arc};
+
+/// A multicodec tagged, length-prefixed payload in the style of a multihash
+///
+/// ```text
+/// <codec-payload> ::= <varuint codec> <varuint length> N(OCTET)
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodecPayload<P = Vec<u8>> {
+    codec: Codec,
+    payload: P,
+}
+
+/// type alias for a CodecPayload base encoded to/from string
+pub type EncodedCodecPayload<P = Vec<u8>> = BaseEncoded<CodecPayload<P>>;
+
+impl<P> CodecPayload<P> {
+    /// create a new codec payload
+    pub fn new(codec: Codec, payload: P) -> Self {
+        Self { codec, payload }
+    }
+
+    /// create an encoded codec payload
+    pub fn encoded_new(base: Base, codec: Codec, payload: P) -> EncodedCodecPayload<P> {
+        BaseEncoded::new(base, Self::new(codec, payload))
+    }
+
+    /// get a reference to the payload
+    pub fn payload(&self) -> &P {
+        &self.payload
+    }
+
+    /// consume self and return the codec and payload
+    pub fn to_inner(self) -> (Codec, P) {
+        (self.codec, self.payload)
+    }
+}
+
+impl<P> CodecInfo for CodecPayload<P> {
+    fn preferred_codec() -> Codec {
+        Codec::Identity
+    }
+
+    fn codec(&self) -> Codec {
+        self.codec
+    }
+}
+
+impl<P> EncodingInfo for CodecPayload<P> {
+    fn preferred_encoding() -> Base {
+        Base::Base16Lower
+    }
+
+    fn encoding(&self) -> Base {
+        Base::Base16Lower
+    }
+}
+
+impl<P> EncodeInto for CodecPayload<P>
+where
+    P: AsRef<[u8]>,
+{
+    fn encode_into(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(self.encoded_len());
+        self.encode_extend(&mut v);
+        v
+    }
+}
+
+impl<P> EncodedLen for CodecPayload<P>
+where
+    P: AsRef<[u8]>,
+{
+    fn encoded_len(&self) -> usize {
+        let len = self.payload.as_ref().len();
+        Varuint(self.codec.code()).encoded_len() + Varuint(len).encoded_len() + len
+    }
+
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        check_len(buf, self.encoded_len())?;
+        let payload = self.payload.as_ref();
+        let mut i = Varuint(self.codec.code()).encode_to_slice(buf)?;
+        i += Varuint(payload.len()).encode_to_slice(&mut buf[i..])?;
+        buf[i..i + payload.len()].copy_from_slice(payload);
+        Ok(i + payload.len())
+    }
+}
+
+impl<P> From<CodecPayload<P>> for Vec<u8>
+where
+    P: AsRef<[u8]>,
+{
+    fn from(cp: CodecPayload<P>) -> Vec<u8> {
+        cp.encode_into()
+    }
+}
+
+/// decode the codec and declared payload length, returning the remaining bytes
+fn decode_header(bytes: &[u8]) -> Result<(Codec, usize, &[u8]), Error> {
+    let (codec, ptr) = Codec::try_decode_from(bytes)?;
+    let (len, ptr) = decode_usize(ptr)?;
+    Ok((codec, len, ptr))
+}
+
+/// decode the payload from exactly the declared number of bytes
+fn decode_payload<'a, P>(codec: Codec, bytes: &'a [u8]) -> Result<P, Error>
+where
+    P: TryFrom<&'a [u8]>,
+    P::Error: StdError + Send + Sync + 'static,
+{
+    P::try_from(bytes).map_err(|e| {
+        CodecPayloadError::PayloadFailed {
+            codec,
+            source: Arc::new(e),
+        }
+        .into()
+    })
+}
+
+impl<'a, P> TryFrom<&'a [u8]> for CodecPayload<P>
+where
+    P: TryFrom<&'a [u8]>,
+    P::Error: StdError + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    /// decode from exactly one encoded codec payload, the declared length must
+    /// match the number of bytes that follow it
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (codec, len, ptr) = decode_header(bytes)?;
+        if ptr.len() != len {
+            return Err(CodecPayloadError::LengthMismatch {
+                declared: len,
+                actual: ptr.len(),
+            }
+            .into());
+        }
+        let payload = decode_payload(codec, ptr)?;
+        Ok(Self { codec, payload })
+    }
+}
+
+impl<'a, P> TryDecodeFrom<'a> for CodecPayload<P>
+where
+    P: TryFrom<&'a [u8]>,
+    P::Error: StdError + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (codec, len, ptr) = decode_header(bytes)?;
+        if ptr.len() < len {
+            return Err(CodecPayloadError::LengthMismatch {
+                declared: len,
+                actual: ptr.len(),
+            }
+            .into());
+        }
+        let payload = decode_payload(codec, &ptr[..len])?;
+        Ok((Self { codec, payload }, &ptr[len..]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let cp1 = CodecPayload::new(Codec::Sha2256, vec![0xaa; 32]);
+        let b = cp1.encode_into();
+        assert_eq!(&[0x12, 0x20], &b[..2]);
+        let (cp2, ptr) = CodecPayload::<Vec<u8>>::try_decode_from(&b).unwrap();
+        assert_eq!(cp1, cp2);
+        assert!(ptr.is_empty());
+    }
+
+    #[test]
+    fn test_fixed_size_payload() {
+        let cp1 = CodecPayload::new(Codec::Sha2256, [0xaa; 32]);
+        let v: Vec<u8> = cp1.clone().into();
+        let cp2 = CodecPayload::<[u8; 32]>::try_from(v.as_slice()).unwrap();
+        assert_eq!(cp1, cp2);
+
+        // the declared length does not match the fixed payload size
+        let short = CodecPayload::new(Codec::Sha2256, [0xaa; 16]).encode_into();
+        assert!(CodecPayload::<[u8; 32]>::try_from(short.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut b = CodecPayload::new(Codec::Sha2256, vec![0xaa; 32]).encode_into();
+        b.truncate(20);
+        match CodecPayload::<Vec<u8>>::try_decode_from(&b) {
+            Err(Error::CodecPayload(CodecPayloadError::LengthMismatch { declared, actual })) => {
+                assert_eq!(32, declared);
+                assert_eq!(18, actual);
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_trailing_bytes() {
+        let mut b = CodecPayload::new(Codec::Sha2256, vec![0xaa; 32]).encode_into();
+        b.push(0);
+        match CodecPayload::<Vec<u8>>::try_from(b.as_slice()) {
+            Err(Error::CodecPayload(CodecPayloadError::LengthMismatch { declared, actual })) => {
+                assert_eq!(32, declared);
+                assert_eq!(33, actual);
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_codec_info() {
+        let cp = CodecPayload::new(Codec::Sha2256, vec![0xaa; 32]);
+        assert_eq!(Codec::Sha2256, cp.codec());
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let cp = CodecPayload::new(Codec::Ed25519Pub, vec![0xaa; 200]);
+        let mut buf = Vec::new();
+        cp.encode_extend(&mut buf);
+        assert_eq!(cp.encode_into(), buf);
+        assert_eq!(buf.len(), cp.encoded_len());
+    }
+
+    #[test]
+    fn test_base_encoded_round_trip() {
+        let ecp1 = CodecPayload::encoded_new(Base::Base58Btc, Codec::Sha2256, vec![0xaa; 32]);
+        let s = ecp1.to_string();
+        let ecp2 = EncodedCodecPayload::try_from(s.as_str()).unwrap();
+        assert_eq!(ecp1, ecp2);
+    }
+}
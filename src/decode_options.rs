@@ -0,0 +1,237 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    base_util::{first_invalid_char, letter_case},
+    prelude::Base,
+    BaseEncoder,
+};
+use std::borrow::Cow;
+
+/// The case of the symbols of a case-insensitive base
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Case {
+    /// lower case symbols, e.g. [`Base::Base32Lower`]
+    Lower,
+    /// upper case symbols, e.g. [`Base::Base32Upper`]
+    Upper,
+}
+
+/// Options for normalizing hand-copied strings before decoding them, e.g.
+/// base32 CIDs pasted in upper case, with surrounding whitespace, or with line
+/// breaks from emails. all options are off by default.
+///
+/// ```
+/// use multiutil::prelude::*;
+///
+/// let s = " BAEAQ\n";
+/// let (v, n) = EncodedVarbytes::try_decode_with(s, &DecodeOptions::lenient()).unwrap();
+/// assert_eq!(vec![1], *v.to_inner());
+/// assert!(n.trimmed);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DecodeOptions {
+    /// remove leading and trailing whitespace
+    pub trim_whitespace: bool,
+    /// remove line breaks inside the string
+    pub strip_line_breaks: bool,
+    /// fold the symbols of case-insensitive bases to the case of the base
+    /// named by the multibase prefix, or of the base of a bare encoder
+    pub fold_case: bool,
+    /// convert case-insensitive multibase strings to the base with the given
+    /// case, e.g. [`Base::Base32Upper`] to [`Base::Base32Lower`]
+    pub normalize_case: Option<Case>,
+}
+
+/// The normalization that [`DecodeOptions`] applied to a string. only the
+/// steps that changed the string are reported.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Normalization {
+    /// leading or trailing whitespace was removed
+    pub trimmed: bool,
+    /// line breaks inside the string were removed
+    pub line_breaks_stripped: bool,
+    /// the case of the symbols was folded to match the base
+    pub case_folded: bool,
+    /// the string was converted to another case from the given base
+    pub case_normalized_from: Option<Base>,
+}
+
+impl Normalization {
+    /// true if the string was decoded exactly as given
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl DecodeOptions {
+    /// options that accept trimmed, wrapped and case-folded strings without
+    /// changing the base they are encoded in
+    pub fn lenient() -> Self {
+        Self {
+            trim_whitespace: true,
+            strip_line_breaks: true,
+            fold_case: true,
+            normalize_case: None,
+        }
+    }
+
+    /// normalize a string for decoding with the given encoder
+    pub fn normalize<'a, Enc: BaseEncoder>(&self, s: &'a str) -> (Cow<'a, str>, Normalization) {
+        let mut n = Normalization::default();
+        let mut s = Cow::Borrowed(s);
+
+        if self.trim_whitespace && s.trim().len() != s.len() {
+            s = Cow::Owned(s.trim().to_string());
+            n.trimmed = true;
+        }
+
+        if self.strip_line_breaks && s.contains(['\r', '\n']) {
+            s = Cow::Owned(s.replace(['\r', '\n'], ""));
+            n.line_breaks_stripped = true;
+        }
+
+        // find the base from the multibase prefix, or from the encoder if it
+        // is a bare encoder. case is only changed when every symbol is valid
+        // afterwards so that a string that only looks prefixed, e.g. a bare
+        // base58 string given to the DetectedEncoder, is left alone.
+        let prefixed = !Enc::encoded_prefix(Base::Identity).is_empty();
+        let (base, data) = if prefixed {
+            let mut chars = s.chars();
+            match chars.next().map(Base::from_code) {
+                Some(Ok(base)) => (base, chars.as_str()),
+                _ => return (s, n),
+            }
+        } else {
+            (Enc::preferred_encoding(Base::Identity), s.as_ref())
+        };
+
+        if let (Some(case), true) = (self.normalize_case, prefixed) {
+            if let Some(to) = with_case(base, case) {
+                let data = fold(data, case);
+                if to != base && first_invalid_char(to, &data, false).is_none() {
+                    let s = format!("{}{}", to.code(), data);
+                    n.case_normalized_from = Some(base);
+                    return (Cow::Owned(s), n);
+                }
+            }
+        }
+
+        if let (Some(case), true) = (letter_case(base), self.fold_case) {
+            let folded = fold(data, case);
+            if folded != data && first_invalid_char(base, &folded, false).is_none() {
+                let prefix = &s[..s.len() - data.len()];
+                let s = format!("{}{}", prefix, folded);
+                n.case_folded = true;
+                return (Cow::Owned(s), n);
+            }
+        }
+
+        (s, n)
+    }
+}
+
+fn fold(s: &str, case: Case) -> String {
+    match case {
+        Case::Lower => s.to_lowercase(),
+        Case::Upper => s.to_uppercase(),
+    }
+}
+
+/// the variant of a case-insensitive base with the given case
+fn with_case(b: Base, case: Case) -> Option<Base> {
+    use Base::*;
+    let (lower, upper) = match b {
+        Base16Lower | Base16Upper => (Base16Lower, Base16Upper),
+        Base32Lower | Base32Upper => (Base32Lower, Base32Upper),
+        Base32PadLower | Base32PadUpper => (Base32PadLower, Base32PadUpper),
+        Base32HexLower | Base32HexUpper => (Base32HexLower, Base32HexUpper),
+        Base32HexPadLower | Base32HexPadUpper => (Base32HexPadLower, Base32HexPadUpper),
+        Base36Lower | Base36Upper => (Base36Lower, Base36Upper),
+        _ => return None,
+    };
+    match case {
+        Case::Lower => Some(lower),
+        Case::Upper => Some(upper),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base_marker, BareEncoder, Base58Encoder, DetectedEncoder, EncodedVarbytes, EncodingInfo,
+        MultibaseEncoder,
+    };
+
+    #[test]
+    fn test_default_is_exact() {
+        let (s, n) = DecodeOptions::default().normalize::<MultibaseEncoder>(" BAEAQ\n");
+        assert_eq!(" BAEAQ\n", s);
+        assert!(n.is_empty());
+        assert!(matches!(s, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_trim_and_strip() {
+        let (s, n) = DecodeOptions::lenient().normalize::<MultibaseEncoder>(" bae\r\naq\n");
+        assert_eq!("baeaq", s);
+        assert!(n.trimmed);
+        assert!(n.line_breaks_stripped);
+        assert!(!n.case_folded);
+    }
+
+    #[test]
+    fn test_fold_case() {
+        let (s, n) = DecodeOptions::lenient().normalize::<MultibaseEncoder>("bAEaq");
+        assert_eq!("baeaq", s);
+        assert!(n.case_folded);
+
+        // the prefix decides the case
+        let (s, n) = DecodeOptions::lenient().normalize::<MultibaseEncoder>("BaEAQ");
+        assert_eq!("BAEAQ", s);
+        assert!(n.case_folded);
+        assert_eq!(None, n.case_normalized_from);
+    }
+
+    #[test]
+    fn test_case_sensitive_bases_are_not_folded() {
+        let (s, n) = DecodeOptions::lenient().normalize::<MultibaseEncoder>("zAbC");
+        assert_eq!("zAbC", s);
+        assert!(n.is_empty());
+        let (s, _) = DecodeOptions::lenient().normalize::<Base58Encoder>("AbC");
+        assert_eq!("AbC", s);
+        // looks like base32 but has symbols outside of the base32 alphabet
+        let (s, n) = DecodeOptions::lenient().normalize::<DetectedEncoder>("bA1z");
+        assert_eq!("bA1z", s);
+        assert!(n.is_empty());
+    }
+
+    #[test]
+    fn test_fold_bare() {
+        type Hex = BareEncoder<base_marker::Base16Lower>;
+        let (s, n) = DecodeOptions::lenient().normalize::<Hex>("DEADbeef");
+        assert_eq!("deadbeef", s);
+        assert!(n.case_folded);
+    }
+
+    #[test]
+    fn test_normalize_case() {
+        let options = DecodeOptions {
+            normalize_case: Some(Case::Lower),
+            ..DecodeOptions::lenient()
+        };
+        let (v, n) = EncodedVarbytes::try_decode_with("BAEAQ", &options).unwrap();
+        assert_eq!(Some(Base::Base32Upper), n.case_normalized_from);
+        assert_eq!(Base::Base32Lower, v.encoding());
+        assert_eq!("baeaq", v.to_string());
+    }
+
+    #[test]
+    fn test_decode_pasted_cid() {
+        let v1 = EncodedVarbytes::try_from("baeaq").unwrap();
+        assert!(EncodedVarbytes::try_from(" bAE\naq ").is_err());
+        let (v2, n) =
+            EncodedVarbytes::try_decode_with(" bAE\naq ", &DecodeOptions::lenient()).unwrap();
+        assert_eq!(v1, v2);
+        assert!(n.trimmed && n.line_breaks_stripped && n.case_folded);
+    }
+}
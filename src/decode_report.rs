@@ -0,0 +1,79 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use multibase::Base;
+
+/// A record of how a string was decoded by [`crate::BaseEncoded::try_decode_report`],
+/// e.g. for audit logs that must keep the exact text that was received even
+/// when re-encoding produces different text, as it does for bare strings
+/// decoded with the [`crate::DetectedEncoder`].
+///
+/// ```
+/// use multiutil::prelude::*;
+///
+/// let s = "0000000100000001";
+/// let (v, r) = BaseEncoded::<Varbytes, DetectedEncoder>::try_decode_report(s).unwrap();
+/// assert_eq!(s, r.original);
+/// assert_eq!(Base::Base2, r.base);
+/// assert!(!r.prefixed);
+/// assert!(!r.identical);
+/// assert_eq!("00000000100000001", v.to_string());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DecodeReport {
+    /// the string that was decoded
+    pub original: String,
+    /// the base the string was decoded from
+    pub base: Base,
+    /// true if the string had a prefix, false if it was a bare decoding
+    pub prefixed: bool,
+    /// true if re-encoding the value produces the original string
+    pub identical: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_multibase() {
+        let (_, r) = EncodedVarbytes::try_decode_report("f0101").unwrap();
+        assert_eq!(
+            DecodeReport {
+                original: "f0101".to_string(),
+                base: Base::Base16Lower,
+                prefixed: true,
+                identical: true,
+            },
+            r
+        );
+    }
+
+    #[test]
+    fn test_detected() {
+        type Detected = BaseEncoded<Varbytes, DetectedEncoder>;
+
+        let (_, r) = Detected::try_decode_report("f0101").unwrap();
+        assert!(r.prefixed && r.identical);
+
+        // a bare base2 string that starts with the base2 prefix
+        let (v, r) = Detected::try_decode_report("0000000100000001").unwrap();
+        assert_eq!(Base::Base2, r.base);
+        assert!(!r.prefixed);
+        assert!(!r.identical);
+        assert_ne!(r.original, v.to_string());
+    }
+
+    #[test]
+    fn test_bare() {
+        let s = BaseEncoded::<Varbytes, Base58Encoder>::from(Varbytes(vec![1])).to_string();
+        let (_, r) = BaseEncoded::<Varbytes, Base58Encoder>::try_decode_report(&s).unwrap();
+        assert_eq!(Base::Base58Btc, r.base);
+        assert!(!r.prefixed);
+        assert!(r.identical);
+    }
+
+    #[test]
+    fn test_failed() {
+        assert!(EncodedVarbytes::try_decode_report("f01").is_err());
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{error::EncodeError, Error};
+
+/// This trait exposes the length of the binary encoding of an object so that
+/// it can be encoded into caller-provided memory without extra allocations
+pub trait EncodedLen {
+    /// return the number of bytes in the binary encoding
+    fn encoded_len(&self) -> usize;
+
+    /// encode into the front of the given slice and return the number of
+    /// bytes written
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error>;
+
+    /// encode by appending to the given vec, reserving exactly enough space
+    fn encode_extend(&self, v: &mut Vec<u8>) {
+        let start = v.len();
+        v.resize(start + self.encoded_len(), 0);
+        // the slice is exactly encoded_len() bytes so this cannot fail
+        let _ = self.encode_to_slice(&mut v[start..]);
+    }
+}
+
+/// check that a buffer can hold the given number of bytes
+pub(crate) fn check_len(buf: &[u8], needed: usize) -> Result<(), Error> {
+    if buf.len() < needed {
+        Err(EncodeError::BufferTooSmall {
+            needed,
+            available: buf.len(),
+        }
+        .into())
+    } else {
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
 // SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    base_name,
+    prelude::{Base, Codec},
+};
+use std::{error::Error as StdError, sync::Arc};
+
 /// Errors generated by the numeric type impls
 #[derive(Clone, Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -15,6 +21,21 @@ pub enum Error {
     /// BaseEncoder error
     #[error(transparent)]
     BaseEncoder(#[from] BaseEncoderError),
+    /// Varuint error
+    #[error(transparent)]
+    Varuint(#[from] VaruintError),
+    /// Varbytes error
+    #[error(transparent)]
+    Varbytes(#[from] VarbytesError),
+    /// Varmap error
+    #[error(transparent)]
+    Varmap(#[from] VarmapError),
+    /// CodecPayload error
+    #[error(transparent)]
+    CodecPayload(#[from] CodecPayloadError),
+    /// Encoding error
+    #[error(transparent)]
+    Encode(#[from] EncodeError),
     /// Custom error for inner types to use when nothing else works
     #[error("Custom error: {0}")]
     Custom(String),
@@ -35,8 +56,20 @@ pub enum BaseEncodedError {
     #[error(transparent)]
     BaseEncoder(#[from] BaseEncoderError),
     /// Value decoding failed
-    #[error("Failed to decode the tagged value")]
-    ValueFailed,
+    #[error("Failed to decode the tagged value from {} data", base_name(*.base))]
+    ValueFailed {
+        /// the base the value's data was decoded from
+        base: Base,
+        /// the error from decoding the value
+        #[source]
+        source: Arc<dyn StdError + Send + Sync>,
+    },
+    /// Every candidate decoding failed to decode as the tagged value
+    #[error("Failed to decode the tagged value from any candidate: {}", chains(.attempts))]
+    AllValuesFailed {
+        /// the failure for each candidate decoding
+        attempts: Vec<BaseEncodedError>,
+    },
 }
 
 /// Errors generated by the base encoding smart pointer
@@ -47,7 +80,226 @@ pub enum BaseEncoderError {
     #[error(transparent)]
     Multibase(#[from] multibase::Error),
 
-    /// Base58 decode error
-    #[error("Base58 error: {0}")]
-    Base58(String),
+    /// Decoding with a specific base failed
+    #[error("Failed to decode {} data{}", base_name(*.base), at_offset(.offset))]
+    DecodeFailed {
+        /// the base the decoding attempted
+        base: Base,
+        /// the character offset of the first invalid symbol, if known
+        offset: Option<usize>,
+        /// the error from the base decoder
+        #[source]
+        source: multibase::Error,
+    },
+
+    /// No base could decode the data
+    #[error("No base could decode the data: {}", chains(.attempts))]
+    DetectionFailed {
+        /// the failure for each base attempted
+        attempts: Vec<BaseEncoderError>,
+    },
+
+    /// The data is encoded in a different base than the one required
+    #[error("Expected {} data, found {}", base_name(*.expected), base_name(*.found))]
+    UnexpectedBase {
+        /// the base that is required
+        expected: Base,
+        /// the base that was found
+        found: Base,
+    },
+
+    /// The base is not block aligned so it can't be streamed
+    #[error("{} is not block aligned and can't be streamed", base_name(*.base))]
+    NotBlockAligned {
+        /// the base that was requested
+        base: Base,
+    },
+
+    /// Bech32 decode error
+    #[cfg(feature = "bech32")]
+    #[error("Bech32 error: {0}")]
+    Bech32(String),
+
+    /// The checksum of a checksummed encoding does not match the data
+    #[error("Invalid {encoding} checksum")]
+    InvalidChecksum {
+        /// the name of the checksummed encoding
+        encoding: &'static str,
+    },
+
+    /// The bech32 human-readable part is not the one required
+    #[cfg(feature = "bech32")]
+    #[error("Expected bech32 human-readable part '{expected}', found '{found}'")]
+    UnexpectedHrp {
+        /// the human-readable part that is required
+        expected: String,
+        /// the human-readable part that was found
+        found: String,
+    },
+
+    /// Unknown base name
+    #[error("Unknown base name '{name}'{}", did_you_mean(.suggestion))]
+    UnknownBaseName {
+        /// the name that was not recognized
+        name: String,
+        /// the closest known base name, if any
+        suggestion: Option<String>,
+    },
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(s) => format!(", did you mean {}?", s),
+        None => String::default(),
+    }
+}
+
+fn at_offset(offset: &Option<usize>) -> String {
+    match offset {
+        Some(o) => format!(" at character {}", o),
+        None => String::default(),
+    }
+}
+
+fn chains<E: StdError>(errors: &[E]) -> String {
+    errors
+        .iter()
+        .map(|e| display_chain(e))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// render an error followed by each error in its source chain
+pub fn display_chain(e: &dyn StdError) -> String {
+    let mut s = e.to_string();
+    let mut source = e.source();
+    while let Some(e) = source {
+        s.push_str(": ");
+        s.push_str(&e.to_string());
+        source = e.source();
+    }
+    s
+}
+
+/// Errors generated by the varuint type
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VaruintError {
+    /// The value does not fit in the target integer width
+    #[error("Varuint value {value} overflows {target}")]
+    Overflow {
+        /// the value that failed to convert
+        value: String,
+        /// the name of the target integer type
+        target: &'static str,
+    },
+    /// The varuint is longer than the maximum length allowed
+    #[error("Varuint is longer than {max_len} bytes")]
+    TooLong {
+        /// the maximum encoded length
+        max_len: usize,
+    },
+    /// The input ends before the varuint does
+    #[error("Varuint is truncated")]
+    Truncated,
+    /// The varuint has trailing zero groups
+    #[error("Varuint is not minimally encoded")]
+    NotMinimal,
+    /// The value is not the representation of any value of the target type
+    #[error("Varuint value {value} is not a known {target}")]
+    UnknownDiscriminant {
+        /// the value that failed to convert
+        value: String,
+        /// the name of the target type
+        target: &'static str,
+    },
+}
+
+/// Errors generated by the varbytes type
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VarbytesError {
+    /// The input is shorter than the declared length
+    #[error("Varbytes truncated: needed {needed} bytes, {available} available")]
+    Truncated {
+        /// the number of bytes declared by the length prefix
+        needed: usize,
+        /// the number of bytes remaining in the input
+        available: usize,
+    },
+}
+
+/// Errors generated by the varmap type
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum VarmapError {
+    /// The same key appears more than once
+    #[error("Varmap duplicate key {key}")]
+    DuplicateKey {
+        /// the duplicated key
+        key: String,
+    },
+    /// A key is not greater than the key before it
+    #[error("Varmap key {key} follows key {previous}, keys must be sorted")]
+    UnsortedKeys {
+        /// the key before the unsorted key
+        previous: String,
+        /// the unsorted key
+        key: String,
+    },
+    /// A key failed to decode
+    #[error("Failed to decode Varmap key {index}")]
+    KeyFailed {
+        /// the index of the key's entry
+        index: usize,
+        /// the error from decoding the key
+        #[source]
+        source: Arc<dyn StdError + Send + Sync>,
+    },
+    /// The value for a key failed to decode
+    #[error("Failed to decode the Varmap value for key {key}")]
+    ValueFailed {
+        /// the key of the value
+        key: String,
+        /// the error from decoding the value
+        #[source]
+        source: Arc<dyn StdError + Send + Sync>,
+    },
+}
+
+/// Errors generated by the codec payload type
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CodecPayloadError {
+    /// The declared payload length does not match the available bytes
+    #[error("CodecPayload length mismatch: declared {declared} bytes, {actual} available")]
+    LengthMismatch {
+        /// the number of bytes declared by the length prefix
+        declared: usize,
+        /// the number of bytes following the length prefix
+        actual: usize,
+    },
+    /// The payload failed to decode
+    #[error("Failed to decode the {codec:?} payload")]
+    PayloadFailed {
+        /// the codec tagging the payload
+        codec: Codec,
+        /// the error from decoding the payload
+        #[source]
+        source: Arc<dyn StdError + Send + Sync>,
+    },
+}
+
+/// Errors generated when encoding into caller-provided memory
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// The output buffer is too small for the encoding
+    #[error("Buffer too small: needed {needed} bytes, {available} available")]
+    BufferTooSmall {
+        /// the number of bytes the encoding requires
+        needed: usize,
+        /// the number of bytes in the buffer
+        available: usize,
+    },
 }
@@ -8,22 +8,66 @@
     unused_qualifications
 )]
 
+/// Arbitrary impls for fuzzing
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+
 /// BaseEncoded smart pointer
 pub mod base_encoded;
-pub use base_encoded::BaseEncoded;
+pub use base_encoded::{BaseEncoded, FixedBaseEncoded};
 
 /// BaseEncoder trait and impls
 pub mod base_encoder;
-pub use base_encoder::{Base58Encoder, BaseEncoder, DetectedEncoder, MultibaseEncoder};
+pub use base_encoder::{
+    BareEncoder, Base58Encoder, BaseEncoder, DetectedEncoder, FixedEncoder, MultibaseEncoder,
+};
+#[cfg(feature = "base58check")]
+pub use base_encoder::Base58CheckEncoder;
+#[cfg(feature = "bech32")]
+pub use base_encoder::{Bech32Encoder, Bech32Hrp};
+
+/// Zero-sized marker types for selecting a base at compile time
+pub mod base_marker;
+pub use base_marker::BaseMarker;
+
+/// Streaming multibase encoding and decoding
+pub mod base_stream;
+pub use base_stream::{MultibaseReader, MultibaseWriter};
 
 /// Base related utility functions / types
 pub mod base_util;
-pub use base_util::{base_name, BaseIter};
+pub use base_util::{base_from_name, base_name, BaseIter};
+
+/// bytes::Buf and bytes::BufMut extensions
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[cfg(feature = "bytes")]
+pub use bytes::{VarBuf, VarBufMut};
+
+/// CachedBaseEncoded smart pointer that memoizes its encoding
+pub mod cached_base_encoded;
+pub use cached_base_encoded::CachedBaseEncoded;
+
+/// CodecPayload type for codec tagged, length-prefixed payloads
+pub mod codec_payload;
+pub use codec_payload::{CodecPayload, EncodedCodecPayload};
 
 /// CodecInfo trait
 pub mod codec_info;
 pub use codec_info::CodecInfo;
 
+/// DecodeOptions for normalizing strings before decoding
+pub mod decode_options;
+pub use decode_options::{Case, DecodeOptions, Normalization};
+
+/// DecodeReport for recording how a string was decoded
+pub mod decode_report;
+pub use decode_report::DecodeReport;
+
+/// EncodedLen trait
+pub mod encoded_len;
+pub use encoded_len::EncodedLen;
+
 /// EncodingInfo trait
 pub mod encoding_info;
 pub use encoding_info::EncodingInfo;
@@ -32,23 +76,64 @@ pub use encoding_info::EncodingInfo;
 pub mod error;
 pub use error::Error;
 
+/// Varuint support for arbitrary-precision integers
+#[cfg(feature = "num-bigint")]
+pub mod num_bigint;
+
+/// Proptest strategies for valid and malformed values
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+/// JsonSchema impls for the string form of encoded values
+#[cfg(feature = "schemars")]
+pub mod schemars;
+
 /// Serde serialization
 #[cfg(feature = "serde")]
 pub mod serde;
 
+/// ConstantTimeEq impls for constant-time comparison
+#[cfg(feature = "subtle")]
+pub mod subtle;
+
 /// Varbytes type for forcing serde of Vec<u8> to/from bytes
 pub mod varbytes;
 pub use varbytes::{EncodedVarbytes, Varbytes};
 
+/// Varmap type for maps of varuint keys to varbytes values
+pub mod varmap;
+pub use varmap::{EncodedVarmap, Varmap};
+
 /// Varunit type for handling serde of numeric types
 pub mod varuint;
 pub use varuint::{EncodedVaruint, Varuint};
 
+/// Word-at-a-time varuint decoding
+pub mod varuint_decode;
+pub use varuint_decode::{decode_u64, decode_usize};
+
+/// VaruintRepr trait for types encoded as varuints through an integer
+pub mod varuint_repr;
+pub use varuint_repr::VaruintRepr;
+#[cfg(feature = "derive")]
+pub use multiutil_derive::VaruintRepr;
+
+// lets the derive macros' ::multiutil paths resolve in this crate's tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as multiutil;
+
+/// SecretVarbytes type for zeroizing secret data
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
+#[cfg(feature = "zeroize")]
+pub use zeroize::SecretVarbytes;
+
 /// one-stop shop for all exported symbols
 pub mod prelude {
     pub use super::{
-        base_encoded::*, base_encoder::*, base_util::*, codec_info::*, encoding_info::*, error::*,
-        varbytes::*, varuint::*,
+        base_encoded::*, base_encoder::*, base_stream::*, base_util::*, cached_base_encoded::*,
+        codec_info::*, codec_payload::*, decode_options::*, decode_report::*, encoded_len::*,
+        encoding_info::*, error::*, varbytes::*, varmap::*, varuint::*, varuint_repr::*,
     };
 
     /// re-exports
@@ -132,6 +217,249 @@ mod test {
         assert_eq!("f42aa".to_string(), betu.to_string());
     }
 
+    #[test]
+    fn test_display_precision() {
+        let ev = Varbytes::encoded_new(Base::Base16Lower, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!("f04deadbeef", format!("{}", ev));
+        assert_eq!("f04de", format!("{:.5}", ev));
+        assert_eq!("f04de     |", format!("{:10.5}|", ev));
+    }
+
+    #[test]
+    fn test_display_abbreviated() {
+        let ev = Varbytes::encoded_new(Base::Base58Btc, (0..32).collect());
+        let s = ev.to_string();
+        assert_eq!(
+            format!("{}…{}", &s[..4], &s[s.len() - 3..]),
+            format!("{:#}", ev)
+        );
+        assert_eq!(
+            format!("{}…{}", &s[..7], &s[s.len() - 6..]),
+            format!("{:#.6}", ev)
+        );
+
+        // bare encodings have no prefix to keep
+        let ev = BaseEncoded::<Varbytes, Base58Encoder>::new(Base::Base58Btc, ev.to_inner());
+        let s = ev.to_string();
+        assert_eq!(
+            format!("{}…{}", &s[..3], &s[s.len() - 3..]),
+            format!("{:#}", ev)
+        );
+
+        // short encodings are not abbreviated
+        assert_eq!("f42aa", format!("{:#}", Unit::encoded_default()));
+    }
+
+    #[test]
+    fn test_lower_hex() {
+        let ev = Varbytes::encoded_new(Base::Base58Btc, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!("04deadbeef", format!("{:x}", ev));
+        assert_eq!("0x04deadbeef", format!("{:#x}", ev));
+        assert_eq!("04dea", format!("{:.5x}", ev));
+    }
+
+    #[test]
+    fn test_bare_round_trip() {
+        use crate::base_marker::{Base16Lower, Base32Lower, Base64Url};
+
+        let be1 = BaseEncoded::<Varbytes, BareEncoder<Base64Url>>::from(Varbytes(vec![0xfb, 0xff]));
+        assert_eq!("Avv_", be1.to_string());
+        let be2 = BaseEncoded::<Varbytes, BareEncoder<Base64Url>>::try_from("Avv_").unwrap();
+        assert_eq!(be1, be2);
+
+        let be = BaseEncoded::<Varbytes, BareEncoder<Base32Lower>>::from(Varbytes(vec![1]));
+        assert_eq!("aeaq", be.to_string());
+
+        // the requested base is overruled by the encoder
+        let be = BaseEncoded::<Varbytes, BareEncoder<Base16Lower>>::new(
+            Base::Base58Btc,
+            Varbytes(vec![1]),
+        );
+        assert_eq!("0101", be.to_string());
+        assert_eq!(
+            Base::Base16Lower,
+            BaseEncoded::<Varbytes, BareEncoder<Base16Lower>>::preferred_encoding()
+        );
+    }
+
+    #[test]
+    fn test_new_keeps_base() {
+        // only fixed encoders overrule the requested base
+        let be = BaseEncoded::<Varbytes, Base58Encoder>::new(Base::Base16Lower, Varbytes(vec![1]));
+        assert_eq!(Base::Base16Lower, be.encoding());
+        let be = BaseEncoded::<Varbytes, DetectedEncoder>::new(Base::Base32Z, Varbytes(vec![1]));
+        assert_eq!(Base::Base32Z, be.encoding());
+    }
+
+    #[test]
+    fn test_bare_decode_failed() {
+        use crate::base_marker::Base64Url;
+
+        // a standard base64 symbol is not in the url safe alphabet
+        let e = BaseEncoded::<Varbytes, BareEncoder<Base64Url>>::try_from("Avv/").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DecodeFailed { base, offset, .. }) => {
+                assert_eq!(Base::Base64Url, base);
+                assert_eq!(Some(3), offset);
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        use crate::base_marker::Base58Btc;
+
+        type DidKey = FixedBaseEncoded<Varbytes, Base58Btc>;
+        let k1 = DidKey::from(Varbytes(vec![0xed, 0x01]));
+        let s = k1.to_string();
+        assert!(s.starts_with('z'));
+        let k2 = DidKey::try_from(s.as_str()).unwrap();
+        assert_eq!(k1, k2);
+
+        // the base can't be overruled at runtime
+        let k3 = DidKey::new(Base::Base16Lower, Varbytes(vec![0xed, 0x01]));
+        assert_eq!(Base::Base58Btc, k3.encoding());
+        assert_eq!(s, k3.to_string());
+    }
+
+    #[test]
+    fn test_fixed_base_rejects_other_prefixes() {
+        use crate::base_marker::Base58Btc;
+
+        let s = Varbytes::encoded_new(Base::Base16Lower, vec![0xed, 0x01]).to_string();
+        match FixedBaseEncoded::<Varbytes, Base58Btc>::try_from(s.as_str()).unwrap_err() {
+            Error::BaseEncoder(BaseEncoderError::UnexpectedBase { expected, found }) => {
+                assert_eq!(Base::Base58Btc, expected);
+                assert_eq!(Base::Base16Lower, found);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_conversions() {
+        use crate::base_marker::{Base32Lower, Base58Btc};
+
+        let be1 = Varbytes::encoded_new(Base::Base58Btc, vec![0xed, 0x01]);
+        let fbe = FixedBaseEncoded::<Varbytes, Base58Btc>::try_from(be1.clone()).unwrap();
+        assert_eq!(be1.to_string(), fbe.to_string());
+        let be2: EncodedVarbytes = fbe.into();
+        assert_eq!(be1, be2);
+        assert!(FixedBaseEncoded::<Varbytes, Base32Lower>::try_from(be2).is_err());
+    }
+
+    #[cfg(feature = "base58check")]
+    #[test]
+    fn test_base58check() {
+        type Base58CheckUnit = BaseEncoded<Unit, Base58CheckEncoder>;
+        let u1 = Base58CheckUnit::from(Unit::default());
+        assert_eq!("aCS1BvXN", u1.to_string());
+        let u2 = Base58CheckUnit::try_from("aCS1BvXN").unwrap();
+        assert_eq!(u1, u2);
+        assert_eq!("aCS…vXN", format!("{:#.3}", u1));
+        assert_eq!("Base58Check ('z') - Unit([66, 170])", format!("{:?}", u1));
+
+        for s in ["aCS1BvXM", "1"] {
+            match Base58CheckUnit::try_from(s).unwrap_err() {
+                Error::BaseEncoder(BaseEncoderError::InvalidChecksum { encoding }) => {
+                    assert_eq!("Base58Check", encoding)
+                }
+                e => panic!("unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    #[cfg(feature = "base58check")]
+    #[test]
+    fn test_base58check_address() {
+        // a version 0 address of an all zero hash
+        let s = Base58CheckEncoder::to_base_encoded(Base::Base58Btc, &[0u8; 21]);
+        assert_eq!("1111111111111111111114oLvT2", s);
+    }
+
+    #[cfg(feature = "bech32")]
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    struct Test;
+    #[cfg(feature = "bech32")]
+    impl Bech32Hrp for Test {
+        const HRP: &'static str = "test";
+    }
+
+    #[cfg(feature = "bech32")]
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    struct Other;
+    #[cfg(feature = "bech32")]
+    impl Bech32Hrp for Other {
+        const HRP: &'static str = "other";
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32() {
+        type Bech32Unit = BaseEncoded<Unit, Bech32Encoder<Test>>;
+        let u1 = Bech32Unit::from(Unit::default());
+        assert_eq!("test1g24q05265j", u1.to_string());
+        let u2 = Bech32Unit::try_from("test1g24q05265j").unwrap();
+        assert_eq!(u1, u2);
+        let u3 = Bech32Unit::try_from("TEST1G24Q05265J").unwrap();
+        assert_eq!(u1, u3);
+        assert_eq!("test1g24…65j", format!("{:#}", u1));
+
+        match Bech32Unit::try_from("test1g24q05265k").unwrap_err() {
+            Error::BaseEncoder(BaseEncoderError::InvalidChecksum { encoding }) => {
+                assert_eq!("Bech32", encoding)
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(matches!(
+            Bech32Unit::try_from("test1G24q05265j").unwrap_err(),
+            Error::BaseEncoder(BaseEncoderError::Bech32(_))
+        ));
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32_valid_hrp() {
+        use crate::base_encoder::valid_hrp;
+
+        for hrp in ["bc", "test", "a", "!~", &"a".repeat(83)] {
+            assert!(valid_hrp(hrp), "{}", hrp);
+        }
+        for hrp in ["", "BC", "Bc", "b c", "b\u{e9}", &"a".repeat(84)] {
+            assert!(!valid_hrp(hrp), "{}", hrp);
+        }
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32_max_len() {
+        // 49 bytes with the "test" hrp is exactly 90 characters
+        let s = Bech32Encoder::<Test>::to_base_encoded(Base::Base32Lower, &[0; 49]);
+        assert_eq!(90, s.len());
+        assert!(Bech32Encoder::<Test>::from_base_encoded(&s).is_ok());
+
+        let s = Bech32Encoder::<Test>::to_base_encoded(Base::Base32Lower, &[0; 50]);
+        assert_eq!(91, s.len());
+        assert!(matches!(
+            Bech32Encoder::<Test>::from_base_encoded(&s).unwrap_err(),
+            Error::BaseEncoder(BaseEncoderError::Bech32(_))
+        ));
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32_unexpected_hrp() {
+        let s = BaseEncoded::<Unit, Bech32Encoder<Other>>::from(Unit::default()).to_string();
+        match BaseEncoded::<Unit, Bech32Encoder<Test>>::try_from(s.as_str()).unwrap_err() {
+            Error::BaseEncoder(BaseEncoderError::UnexpectedHrp { expected, found }) => {
+                assert_eq!("test", expected);
+                assert_eq!("other", found);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn test_legacy_display() {
         let betu = Unit::base58_encoded_default();
@@ -150,6 +478,80 @@ mod test {
         assert_eq!(Unit::base58_encoded_default(), betu);
     }
 
+    #[test]
+    fn test_value_failed_source() {
+        use std::error::Error as _;
+        let e = EncodedUnit::try_from("f42").unwrap_err();
+        match e {
+            Error::BaseEncoded(BaseEncodedError::ValueFailed { base, .. }) => {
+                assert_eq!(Base::Base16Lower, base)
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+        let source = e.source().unwrap();
+        assert_eq!("Custom error: too few items in the vec", source.to_string());
+    }
+
+    #[test]
+    fn test_decode_failed_offset() {
+        let e = EncodedUnit::try_from("f42zz").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DecodeFailed { base, offset, .. }) => {
+                assert_eq!(Base::Base16Lower, base);
+                assert_eq!(Some(3), offset);
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_decode_failed_offset_ignores_case() {
+        // permissive decoding accepts the upper case 'A' so the 'G' is the
+        // first invalid symbol
+        let e = EncodedUnit::try_from("f42AG").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DecodeFailed { offset, .. }) => {
+                assert_eq!(Some(4), offset);
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+
+        // every symbol is valid so the odd length is reported without one
+        let e = EncodedUnit::try_from("f42A").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DecodeFailed { base, offset, .. }) => {
+                assert_eq!(Base::Base16Lower, base);
+                assert_eq!(None, offset);
+                assert!(!e.to_string().contains("at character"));
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_base58_decode_failed_offset() {
+        let e = Base58EncodedUnit::try_from("65l").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DecodeFailed { base, offset, .. }) => {
+                assert_eq!(Base::Base58Btc, base);
+                assert_eq!(Some(2), offset);
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_detection_failed_attempts() {
+        let e = BaseEncoded::<Unit, DetectedEncoder>::try_from("!!").unwrap_err();
+        match e {
+            Error::BaseEncoder(BaseEncoderError::DetectionFailed { attempts }) => {
+                // the prefixed attempt plus every bare base after Identity
+                assert_eq!(BaseIter::new().count(), attempts.len());
+            }
+            _ => panic!("unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn test_string_round_trip() {
         let betu1 = Unit::encoded_default();
@@ -172,6 +574,15 @@ mod test {
         assert_eq!(betu.value(), 0x42);
     }
 
+    #[test]
+    fn test_encoded_len() {
+        let ev = Varbytes::encoded_new(Base::Base58Btc, vec![1, 2, 3]);
+        let mut buf = Vec::new();
+        ev.encode_extend(&mut buf);
+        assert_eq!(ev.encoded_len(), buf.len());
+        assert_eq!(vec![3, 1, 2, 3], buf);
+    }
+
     #[test]
     fn test_as_ref() {
         let betu = Unit::encoded_default();
@@ -0,0 +1,157 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`crate::Varuint`] support for arbitrary-precision [`BigUint`] values.
+//! Decoding is bounded by a maximum encoded length, [`MAX_BIGUINT_LEN`] by
+//! default, so that untrusted input can't force unbounded work. `TryFrom`,
+//! `TryDecodeFrom`, `BaseEncoded` and serde always use [`MAX_BIGUINT_LEN`];
+//! use [`Varuint::try_decode_with_max_len`] on the bytes for any other limit.
+//! There are no signed varints in this crate so there is no `BigInt` support.
+use crate::{encoded_len::check_len, error::VaruintError, EncodedLen, Error, Varuint};
+use core::fmt;
+use multitrait::TryDecodeFrom;
+use num_bigint::BigUint;
+
+/// the maximum encoded length of a big varuint used by `TryFrom`,
+/// `TryDecodeFrom`, `BaseEncoded` and serde, enough for 7168 bits
+pub const MAX_BIGUINT_LEN: usize = 1024;
+
+impl Varuint<BigUint> {
+    /// decode a varuint that is at most max_len bytes long, returning the
+    /// remaining bytes
+    pub fn try_decode_with_max_len(bytes: &[u8], max_len: usize) -> Result<(Self, &[u8]), Error> {
+        let len = match bytes.iter().take(max_len).position(|b| b & 0x80 == 0) {
+            Some(i) => i + 1,
+            None if bytes.len() > max_len => return Err(VaruintError::TooLong { max_len }.into()),
+            None => return Err(VaruintError::Truncated.into()),
+        };
+        // a final zero group only adds length, except when encoding zero
+        if len > 1 && bytes[len - 1] == 0 {
+            return Err(VaruintError::NotMinimal.into());
+        }
+        let digits: Vec<u8> = bytes[..len].iter().map(|b| b & 0x7f).collect();
+        // the digits are all less than 128 so this can't fail
+        let n = BigUint::from_radix_le(&digits, 128).unwrap_or_default();
+        Ok((Self(n), &bytes[len..]))
+    }
+}
+
+impl fmt::Debug for Varuint<BigUint> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", Vec::from(self.clone()).as_slice())
+    }
+}
+
+impl EncodedLen for Varuint<BigUint> {
+    fn encoded_len(&self) -> usize {
+        (self.0.bits() as usize).div_ceil(7).max(1)
+    }
+
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let len = EncodedLen::encoded_len(self);
+        check_len(buf, len)?;
+        // to_radix_le encodes zero as a single zero digit
+        let digits = self.0.to_radix_le(128);
+        for (b, d) in buf.iter_mut().zip(digits.iter()) {
+            *b = d | 0x80;
+        }
+        buf[len - 1] &= 0x7f;
+        Ok(len)
+    }
+}
+
+impl From<Varuint<BigUint>> for Vec<u8> {
+    fn from(vu: Varuint<BigUint>) -> Vec<u8> {
+        let mut v = vec![0u8; vu.encoded_len()];
+        // the buffer is exactly the encoded length so this cannot fail
+        let _ = vu.encode_to_slice(&mut v);
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Varuint<BigUint> {
+    type Error = Error;
+
+    fn try_from(s: &'a [u8]) -> Result<Self, Error> {
+        let (v, _) = Self::try_decode_from(s)?;
+        Ok(v)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for Varuint<BigUint> {
+    type Error = Error;
+
+    /// decode a varuint of at most [`MAX_BIGUINT_LEN`] bytes
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        Self::try_decode_with_max_len(bytes, MAX_BIGUINT_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use multitrait::EncodeInto;
+
+    #[test]
+    fn test_matches_u128() {
+        for n in [
+            0_u128,
+            1,
+            0x7f,
+            0x80,
+            0x3fff,
+            0x4000,
+            u64::MAX.into(),
+            u128::MAX,
+        ] {
+            let big: Vec<u8> = Varuint(BigUint::from(n)).into();
+            assert_eq!(Varuint(n).encode_into(), big);
+            assert_eq!(
+                Varuint(n).encoded_len(),
+                Varuint(BigUint::from(n)).encoded_len()
+            );
+            let (v, rest) = Varuint::<BigUint>::try_decode_from(&big).unwrap();
+            assert_eq!(BigUint::from(n), v.0);
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_beyond_u128() {
+        let n = BigUint::from(u128::MAX) * BigUint::from(u128::MAX) + 1_u32;
+        let v: Vec<u8> = Varuint(n.clone()).into();
+        assert_eq!(37, v.len());
+        assert_eq!(n, Varuint::<BigUint>::try_from(v.as_slice()).unwrap().0);
+    }
+
+    #[test]
+    fn test_too_long() {
+        let v: Vec<u8> = Varuint(BigUint::from(1_u8) << 700_u32).into();
+        assert_eq!(101, v.len());
+        assert!(Varuint::<BigUint>::try_decode_with_max_len(&v, 101).is_ok());
+        match Varuint::<BigUint>::try_decode_with_max_len(&v, 100).unwrap_err() {
+            Error::Varuint(VaruintError::TooLong { max_len }) => assert_eq!(100, max_len),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(Varuint::<BigUint>::try_decode_from(&[0xff; MAX_BIGUINT_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(matches!(
+            Varuint::<BigUint>::try_decode_from(&[0x80, 0x80]).unwrap_err(),
+            Error::Varuint(VaruintError::Truncated)
+        ));
+        assert!(matches!(
+            Varuint::<BigUint>::try_decode_from(&[0x81, 0x00]).unwrap_err(),
+            Error::Varuint(VaruintError::NotMinimal)
+        ));
+        assert!(Varuint::<BigUint>::try_decode_from(&[]).is_err());
+    }
+
+    #[test]
+    fn test_encoded() {
+        let v = EncodedVaruint::<BigUint>::from(Varuint(BigUint::from(0xed_u32)));
+        assert_eq!("fed01", v.to_string());
+        assert_eq!(v, EncodedVaruint::<BigUint>::try_from("fed01").unwrap());
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`proptest`] strategies for generating valid and deliberately malformed
+//! [`crate::Varuint`], [`crate::Varbytes`] and [`crate::BaseEncoded`] values
+//! and encodings
+use crate::{
+    prelude::Base, BaseEncoded, BaseEncoder, BaseIter, EncodingInfo, Varbytes, Varuint, VaruintRepr,
+};
+use core::fmt;
+use multitrait::EncodeInto;
+use proptest::{arbitrary::Arbitrary, collection, prelude::*, sample};
+
+/// every [`Base`] in [`BaseIter`]
+pub fn base() -> impl Strategy<Value = Base> {
+    sample::select(BaseIter::new().collect::<Vec<_>>())
+}
+
+/// every [`Base`] that can encode arbitrary bytes as a string, which excludes
+/// [`Base::Identity`] since it only encodes UTF-8
+pub fn string_safe_base() -> impl Strategy<Value = Base> {
+    sample::select(
+        BaseIter::new()
+            .filter(|b| *b != Base::Identity)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// any [`Varuint`] of the given integer type
+pub fn varuint<T>() -> impl Strategy<Value = Varuint<T>>
+where
+    T: Arbitrary + VaruintRepr,
+{
+    any::<T>().prop_map(Varuint)
+}
+
+/// any [`Varbytes`] with up to max_len bytes
+pub fn varbytes(max_len: usize) -> impl Strategy<Value = Varbytes> {
+    collection::vec(any::<u8>(), 0..=max_len).prop_map(Varbytes)
+}
+
+/// a [`BaseEncoded`] wrapping values from the inner strategy in any string
+/// safe base, giving the [`BaseEncoder`] a chance to overrule the base
+pub fn base_encoded<T, Enc, S>(inner: S) -> impl Strategy<Value = BaseEncoded<T, Enc>>
+where
+    S: Strategy<Value = T>,
+    T: fmt::Debug + EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder + fmt::Debug,
+{
+    (string_safe_base(), inner).prop_map(|(b, t)| BaseEncoded::new(Enc::preferred_encoding(b), t))
+}
+
+/// varuint encodings that fail to decode as a u64: truncated encodings where
+/// every byte has the continuation bit set, non-minimal encodings that end in
+/// a zero byte, and encodings longer than the 10 bytes a u64 can need
+pub fn malformed_varuint() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        collection::vec(0x80_u8..=0xff, 1..10),
+        collection::vec(0x80_u8..=0xff, 1..9).prop_map(|mut v| {
+            v.push(0x00);
+            v
+        }),
+        collection::vec(0x80_u8..=0xff, 10..16).prop_map(|mut v| {
+            v.push(0x01);
+            v
+        }),
+    ]
+}
+
+/// varbytes encodings whose length prefix declares more bytes than follow
+pub fn malformed_varbytes() -> impl Strategy<Value = Vec<u8>> {
+    (1_usize..256).prop_flat_map(|len| {
+        collection::vec(any::<u8>(), 0..len).prop_map(move |data| {
+            let mut v = len.encode_into();
+            v.extend_from_slice(&data);
+            v
+        })
+    })
+}
+
+/// valid base encoded strings of values from the inner strategy
+pub fn base_encoded_string<T, S>(inner: S) -> impl Strategy<Value = String>
+where
+    S: Strategy<Value = T>,
+    T: fmt::Debug + EncodingInfo + Clone + Into<Vec<u8>>,
+{
+    base_encoded::<T, crate::MultibaseEncoder, S>(inner).prop_map(|be| be.to_string())
+}
+
+/// base encoded strings of values from the inner strategy with a symbol that
+/// is in no base's alphabet inserted. when inserted at the front it is an
+/// unknown multibase prefix.
+pub fn malformed_base_encoded_string<T, S>(inner: S) -> impl Strategy<Value = String>
+where
+    S: Strategy<Value = T>,
+    T: fmt::Debug + EncodingInfo + Clone + Into<Vec<u8>>,
+{
+    (base_encoded_string(inner), any::<sample::Index>()).prop_map(|(s, i)| {
+        let mut s: Vec<char> = s.chars().collect();
+        s.insert(i.index(s.len() + 1), '!');
+        s.into_iter().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EncodedLen, EncodedVarbytes};
+    use multitrait::TryDecodeFrom;
+
+    proptest! {
+        #[test]
+        fn varuint_round_trip(v in varuint::<u64>()) {
+            let b = v.encode_into();
+            prop_assert_eq!(b.len(), v.encoded_len());
+            let (v2, ptr) = Varuint::<u64>::try_decode_from(&b).unwrap();
+            prop_assert_eq!(v, v2);
+            prop_assert!(ptr.is_empty());
+        }
+
+        #[test]
+        fn varuint_u128_encode_to_slice(v in varuint::<u128>()) {
+            let mut b = [0u8; 19];
+            let len = v.encode_to_slice(&mut b).unwrap();
+            prop_assert_eq!(v.encode_into(), b[..len].to_vec());
+        }
+
+        #[test]
+        fn varbytes_round_trip(v in varbytes(1024)) {
+            let b = v.encode_into();
+            prop_assert_eq!(b.len(), EncodedLen::encoded_len(&v));
+            let (v2, ptr) = Varbytes::try_decode_from(&b).unwrap();
+            prop_assert_eq!(v, v2);
+            prop_assert!(ptr.is_empty());
+        }
+
+        #[test]
+        fn base_encoded_round_trip(v in base_encoded::<_, crate::MultibaseEncoder, _>(varbytes(256))) {
+            let s = v.to_string();
+            prop_assert_eq!(v, EncodedVarbytes::try_from(s.as_str()).unwrap());
+        }
+
+        #[test]
+        fn malformed_varuint_fails(b in malformed_varuint()) {
+            prop_assert!(Varuint::<u64>::try_decode_from(&b).is_err());
+        }
+
+        #[test]
+        fn malformed_varbytes_fails(b in malformed_varbytes()) {
+            prop_assert!(Varbytes::try_decode_from(&b).is_err());
+        }
+
+        #[test]
+        fn malformed_base_encoded_fails(s in malformed_base_encoded_string(varbytes(64))) {
+            prop_assert!(EncodedVarbytes::try_from(s.as_str()).is_err());
+        }
+    }
+}
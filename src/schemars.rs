@@ -0,0 +1,198 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`JsonSchema`] impls describing the string form of [`crate::BaseEncoded`]
+//! values, including [`crate::EncodedVaruint`] and [`crate::EncodedVarbytes`]
+use crate::{base_name, BaseEncoded, BaseEncoder, BaseIter, EncodingInfo};
+use multibase::Base;
+use schemars::{
+    schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation},
+    JsonSchema, SchemaGenerator,
+};
+
+impl<T, Enc> JsonSchema for BaseEncoded<T, Enc>
+where
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+{
+    // the pattern depends on T and Enc so the schema is always inlined
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "BaseEncoded".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        let base = Enc::preferred_encoding(T::preferred_encoding());
+        // encoders that keep each value's own base can produce any base
+        let fixed = BaseIter::new().all(|b| Enc::preferred_encoding(b) == base);
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                description: Some(format!(
+                    "{} encoded string, preferred base {}",
+                    if Enc::encoded_prefix(base) == base.code().to_string() {
+                        "Multibase"
+                    } else {
+                        "Base"
+                    },
+                    base_name(base)
+                )),
+                ..Default::default()
+            })),
+            string: Some(Box::new(StringValidation {
+                pattern: if fixed {
+                    pattern::<Enc>(base)
+                } else {
+                    Some(any_pattern::<Enc>())
+                },
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// a regular expression matching the prefix followed by symbols from the
+/// base's alphabet. there is no pattern for bases without a fixed alphabet.
+fn pattern<Enc: BaseEncoder>(base: Base) -> Option<String> {
+    let alphabet = Enc::alphabet(base)?;
+    Some(format!(
+        "^{}[{}]*$",
+        escape(&Enc::encoded_prefix(base)),
+        escape(alphabet)
+    ))
+}
+
+/// a regular expression matching any base's prefix followed by symbols from
+/// that base's alphabet, or by anything for bases without a fixed alphabet
+fn any_pattern<Enc: BaseEncoder>() -> String {
+    let alternatives: Vec<String> = BaseIter::new()
+        .map(|b| {
+            let symbols = match Enc::alphabet(b) {
+                Some(alphabet) => format!("[{}]*", escape(alphabet)),
+                None => "[\\s\\S]*".to_string(),
+            };
+            format!("{}{}", escape(&Enc::encoded_prefix(b)), symbols)
+        })
+        .collect();
+    format!("^(?:{})$", alternatives.join("|"))
+}
+
+/// escape the regular expression syntax characters in s
+fn escape(s: &str) -> String {
+    s.chars().fold(String::new(), |mut e, c| {
+        if "\\^$.|?*+()[]{}-/".contains(c) {
+            e.push('\\');
+        }
+        e.push(c);
+        e
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use schemars::schema_for;
+
+    fn schema_json<T: schemars::JsonSchema>() -> serde_json::Value {
+        serde_json::to_value(schema_for!(T)).unwrap()
+    }
+
+    // the string form of a BaseEncoded is its human readable serialization
+    fn matches(pattern: &serde_json::Value, v: &impl ToString) -> bool {
+        let re = regex::Regex::new(pattern.as_str().unwrap()).unwrap();
+        re.is_match(&v.to_string())
+    }
+
+    #[test]
+    fn test_encoded_varbytes() {
+        let s = schema_json::<EncodedVarbytes>();
+        assert_eq!("string", s["type"]);
+        assert_eq!(
+            "Multibase encoded string, preferred base Base16Lower",
+            s["description"]
+        );
+        let p = &s["pattern"];
+        assert!(p.as_str().unwrap().contains("|f[0123456789abcdef]*|"));
+        assert!(matches(
+            p,
+            &Varbytes::encoded_new(Base::Base16Lower, vec![1, 2])
+        ));
+        assert!(!matches(&s["pattern"], &"f0g"));
+        assert!(!matches(&s["pattern"], &"!0102"));
+    }
+
+    #[test]
+    fn test_non_preferred_base() {
+        // the multibase encoder keeps each value's own base so every base's
+        // output matches the schema
+        let p = &schema_json::<EncodedVarbytes>()["pattern"];
+        for base in BaseIter::new() {
+            // utf-8 data so that the identity base can encode it
+            let v = Varbytes::encoded_new(base, b"multi".to_vec());
+            assert!(matches(p, &v), "{}", v);
+        }
+        let p = &schema_json::<BaseEncoded<Varbytes, DetectedEncoder>>()["pattern"];
+        let v = BaseEncoded::<Varbytes, DetectedEncoder>::new(Base::Base58Btc, Varbytes(vec![1]));
+        assert!(matches(p, &v));
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        let s = schema_json::<FixedBaseEncoded<Varbytes, crate::base_marker::Base32Lower>>();
+        assert_eq!("^b[abcdefghijklmnopqrstuvwxyz234567]*$", s["pattern"]);
+    }
+
+    #[test]
+    fn test_bare_base58() {
+        let s = schema_json::<BaseEncoded<Varbytes, Base58Encoder>>();
+        assert_eq!(
+            "^[123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz]*$",
+            s["pattern"]
+        );
+        assert_eq!(
+            "Base encoded string, preferred base Base58Btc",
+            s["description"]
+        );
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32() {
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        struct Bc;
+        impl Bech32Hrp for Bc {
+            const HRP: &'static str = "bc";
+        }
+        let s = schema_json::<BaseEncoded<Varbytes, Bech32Encoder<Bc>>>();
+        assert_eq!("^bc1[qpzry9x8gf2tvdw0s3jn54khce6mua7l]*$", s["pattern"]);
+    }
+
+    #[test]
+    fn test_escaped_alphabet() {
+        assert_eq!(
+            Some(
+                "^m[ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789\\+\\/]*$"
+                    .to_string()
+            ),
+            super::pattern::<MultibaseEncoder>(Base::Base64)
+        );
+        assert_eq!(None, super::pattern::<MultibaseEncoder>(Base::Base256Emoji));
+    }
+
+    #[test]
+    fn test_inlined_field() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Key {
+            id: EncodedVaruint<u64>,
+            data: EncodedVarbytes,
+        }
+        let s = schema_json::<Key>();
+        let p = &schema_json::<EncodedVarbytes>()["pattern"];
+        assert_eq!(*p, s["properties"]["id"]["pattern"]);
+        assert_eq!(*p, s["properties"]["data"]["pattern"]);
+    }
+}
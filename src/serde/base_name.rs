@@ -0,0 +1,23 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! Serde adapter for [`multibase::Base`] fields that (de)serializes them by
+//! name. Use it with `#[serde(with = "multiutil::serde::base_name")]`.
+use crate::{base_from_name, base_name};
+use multibase::Base;
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+/// Serialize a [`Base`] as its name, e.g. "Base58Btc"
+pub fn serialize<S>(base: &Base, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&base_name(*base))
+}
+
+/// Deserialize a [`Base`] from any name accepted by [`base_from_name`]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Base, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    base_from_name(&s).map_err(|e| de::Error::custom(e.to_string()))
+}
@@ -1,14 +1,20 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::{BaseEncoded, BaseEncoder, EncodingInfo, Varbytes, Varuint};
+use crate::{
+    error::{display_chain, BaseEncoderError, VarmapError},
+    BaseEncoded, BaseEncoder, CachedBaseEncoded, CodecPayload, EncodingInfo, Varbytes, Varmap,
+    Varuint, VaruintRepr,
+};
 use core::{fmt, marker};
 use multibase::Base;
 use multitrait::prelude::TryDecodeFrom;
 use serde::de;
+use std::{collections::BTreeMap, error::Error as StdError, sync::Arc};
 
 /// Deserialize instance of [`crate::BaseEncoded`] from a byte slice
 impl<'de, T, Enc> de::Deserialize<'de> for BaseEncoded<T, Enc>
 where
     T: de::Deserialize<'de> + EncodingInfo + for<'a> TryFrom<&'a [u8]> + ?Sized,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
     Enc: BaseEncoder,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -24,6 +30,7 @@ where
         impl<'de, T, Enc> de::Visitor<'de> for BaseEncodedVisitor<T, Enc>
         where
             T: de::Deserialize<'de> + EncodingInfo + for<'a> TryFrom<&'a [u8]> + ?Sized,
+            for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
             Enc: BaseEncoder,
         {
             type Value = BaseEncoded<T, Enc>;
@@ -40,7 +47,7 @@ where
             where
                 E: de::Error,
             {
-                Self::Value::try_from(s).map_err(|e| de::Error::custom(e.to_string()))
+                Self::Value::try_from(s).map_err(|e| de::Error::custom(display_chain(&e)))
             }
 
             #[inline]
@@ -48,7 +55,7 @@ where
             where
                 E: de::Error,
             {
-                Self::Value::try_from(s).map_err(|e| de::Error::custom(e.to_string()))
+                Self::Value::try_from(s).map_err(|e| de::Error::custom(display_chain(&e)))
             }
 
             // longest lifetime
@@ -58,7 +65,7 @@ where
                 E: de::Error,
             {
                 Self::Value::try_from(s.as_str())
-                    .map_err(|e| de::Error::custom(e.to_string()))
+                    .map_err(|e| de::Error::custom(display_chain(&e)))
             }
 
             // binary
@@ -74,6 +81,16 @@ where
                     }
                 };
 
+                // a fixed encoder only allows one base
+                let expected = Enc::preferred_encoding(base);
+                if Enc::FIXED && expected != base {
+                    let e = BaseEncoderError::UnexpectedBase {
+                        expected,
+                        found: base,
+                    };
+                    return Err(de::Error::custom(display_chain(&e)));
+                }
+
                 let t = match seq.next_element()? {
                     Some(t) => t,
                     None => return Err(de::Error::custom("expected inner type value".to_string())),
@@ -94,10 +111,44 @@ where
     }
 }
 
+/// Deserialize instance of [`crate::CachedBaseEncoded`], keeping the original
+/// string when human readable
+impl<'de, T, Enc> de::Deserialize<'de> for CachedBaseEncoded<T, Enc>
+where
+    T: de::Deserialize<'de> + EncodingInfo + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+    Enc: BaseEncoder,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            Self::try_from(s.as_str()).map_err(|e| de::Error::custom(display_chain(&e)))
+        } else {
+            BaseEncoded::deserialize(deserializer).map(Self::from)
+        }
+    }
+}
+
+/// decode a varuint, reporting why the representation was not a T
+fn decode_varuint<T, E>(v: &[u8]) -> Result<Varuint<T>, E>
+where
+    T: VaruintRepr,
+    E: de::Error,
+{
+    let (repr, _) = T::Repr::try_decode_from(v)
+        .map_err(|_| de::Error::custom("failed to deserialize varuint bytes"))?;
+    T::from_repr(repr)
+        .map(Varuint)
+        .map_err(|e| de::Error::custom(display_chain(&e)))
+}
+
 /// Deserialize instance of [`crate::Varuint`] from a byte slice
 impl<'de, T> de::Deserialize<'de> for Varuint<T>
 where
-    T: for<'a> TryDecodeFrom<'a>,
+    T: VaruintRepr,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -107,7 +158,7 @@ where
 
         impl<'de, T> de::Visitor<'de> for VaruintVisitor<T>
         where
-            T: for<'a> TryDecodeFrom<'a>,
+            T: VaruintRepr,
         {
             type Value = Varuint<T>;
 
@@ -123,9 +174,7 @@ where
             where
                 E: de::Error,
             {
-                let (t, _) = T::try_decode_from(v)
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint bytes"))?;
-                Ok(Varuint(t))
+                decode_varuint(v)
             }
 
             #[inline]
@@ -133,9 +182,7 @@ where
             where
                 E: de::Error,
             {
-                let (t, _) = T::try_decode_from(v)
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint bytes"))?;
-                Ok(Varuint(t))
+                decode_varuint(v)
             }
 
             // longest lifetime
@@ -144,9 +191,7 @@ where
             where
                 E: de::Error,
             {
-                let (t, _) = T::try_decode_from(v.as_slice())
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint bytes"))?;
-                Ok(Varuint(t))
+                decode_varuint(v.as_slice())
             }
 
             // binary / human readable
@@ -162,9 +207,7 @@ where
                 while let Some(b) = seq.next_element()? {
                     v.push(b);
                 }
-                let (t, _) = T::try_decode_from(v.as_slice())
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint bytes"))?;
-                Ok(Varuint(t))
+                decode_varuint(v.as_slice())
             }
         }
 
@@ -195,10 +238,9 @@ impl<'de> de::Deserialize<'de> for Varbytes {
             where
                 E: de::Error,
             {
-                let (len, ptr) = usize::try_decode_from(v)
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint len"))?;
-                let v = ptr[..len].to_vec();
-                Ok(Varbytes(v))
+                let (vb, _) = Varbytes::try_decode_from(v)
+                    .map_err(|e| de::Error::custom(display_chain(&e)))?;
+                Ok(vb)
             }
 
             #[inline]
@@ -206,10 +248,9 @@ impl<'de> de::Deserialize<'de> for Varbytes {
             where
                 E: de::Error,
             {
-                let (len, ptr) = usize::try_decode_from(v)
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint len"))?;
-                let v = ptr[..len].to_vec();
-                Ok(Varbytes(v))
+                let (vb, _) = Varbytes::try_decode_from(v)
+                    .map_err(|e| de::Error::custom(display_chain(&e)))?;
+                Ok(vb)
             }
 
             // longest lifetime
@@ -218,10 +259,9 @@ impl<'de> de::Deserialize<'de> for Varbytes {
             where
                 E: de::Error,
             {
-                let (len, ptr) = usize::try_decode_from(v.as_slice())
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint len"))?;
-                let v = ptr[..len].to_vec();
-                Ok(Varbytes(v))
+                let (vb, _) = Varbytes::try_decode_from(v.as_slice())
+                    .map_err(|e| de::Error::custom(display_chain(&e)))?;
+                Ok(vb)
             }
 
             // binary / human readable
@@ -237,13 +277,277 @@ impl<'de> de::Deserialize<'de> for Varbytes {
                 while let Some(b) = seq.next_element()? {
                     v.push(b);
                 }
-                let (len, ptr) = usize::try_decode_from(v.as_slice())
-                    .map_err(|_| de::Error::custom("failed to deserialize varuint len"))?;
-                let v = ptr[..len].to_vec();
-                Ok(Varbytes(v))
+                let (vb, _) = Varbytes::try_decode_from(v.as_slice())
+                    .map_err(|e| de::Error::custom(display_chain(&e)))?;
+                Ok(vb)
             }
         }
 
         deserializer.deserialize_bytes(VarbytesVisitor)
     }
 }
+
+/// Deserialize instance of [`crate::CodecPayload`] from a byte slice
+impl<'de, P> de::Deserialize<'de> for CodecPayload<P>
+where
+    P: for<'a> TryFrom<&'a [u8]>,
+    for<'a> <P as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct CodecPayloadVisitor<P>(marker::PhantomData<P>);
+
+        impl<'de, P> de::Visitor<'de> for CodecPayloadVisitor<P>
+        where
+            P: for<'a> TryFrom<&'a [u8]>,
+            for<'a> <P as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+        {
+            type Value = CodecPayload<P>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "varuint encoded codec and len followed by bytes")
+            }
+
+            // only binary
+
+            #[inline]
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                CodecPayload::try_from(v).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+
+            // binary / human readable
+
+            // this typically only happens when there are bytes serialized into
+            // a human readable format.
+            #[inline]
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let mut v = Vec::new();
+                while let Some(b) = seq.next_element()? {
+                    v.push(b);
+                }
+                CodecPayload::try_from(v.as_slice())
+                    .map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+        }
+
+        deserializer.deserialize_bytes(CodecPayloadVisitor::<P>(marker::PhantomData))
+    }
+}
+
+/// Deserialize instance of [`crate::Varmap`] from a map of keys to multibase
+/// encoded values when human readable and from its binary encoding otherwise
+impl<'de, K, V> de::Deserialize<'de> for Varmap<K, V>
+where
+    K: de::Deserialize<'de> + Ord + fmt::Debug + for<'a> TryDecodeFrom<'a>,
+    for<'a> <K as TryDecodeFrom<'a>>::Error: StdError + Send + Sync + 'static,
+    V: for<'a> TryFrom<&'a [u8]>,
+    for<'a> <V as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct VarmapVisitor<K, V>(marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> de::Visitor<'de> for VarmapVisitor<K, V>
+        where
+            K: de::Deserialize<'de> + Ord + fmt::Debug + for<'a> TryDecodeFrom<'a>,
+            for<'a> <K as TryDecodeFrom<'a>>::Error: StdError + Send + Sync + 'static,
+            V: for<'a> TryFrom<&'a [u8]>,
+            for<'a> <V as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+        {
+            type Value = Varmap<K, V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "map of keys to multibase values or varmap bytes")
+            }
+
+            // human readable
+
+            fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                let mut map = BTreeMap::new();
+                while let Some(key) = access.next_key::<K>()? {
+                    if map.contains_key(&key) {
+                        return Err(de::Error::custom(VarmapError::DuplicateKey {
+                            key: format!("{:?}", key),
+                        }));
+                    }
+                    let s: String = access.next_value()?;
+                    let (_, v) = multibase::decode(&s, true)
+                        .map_err(|e| de::Error::custom(display_chain(&e)))?;
+                    let value = V::try_from(v.as_slice()).map_err(|e| {
+                        de::Error::custom(display_chain(&VarmapError::ValueFailed {
+                            key: format!("{:?}", key),
+                            source: Arc::new(e),
+                        }))
+                    })?;
+                    map.insert(key, value);
+                }
+                Ok(Varmap::from(map))
+            }
+
+            // binary
+
+            #[inline]
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Varmap::try_from(v).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+
+            // binary / human readable
+
+            // this typically only happens when there are bytes serialized into
+            // a human readable format.
+            #[inline]
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let mut v = Vec::new();
+                while let Some(b) = seq.next_element()? {
+                    v.push(b);
+                }
+                Varmap::try_from(v.as_slice()).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_map(VarmapVisitor::<K, V>(marker::PhantomData))
+        } else {
+            deserializer.deserialize_bytes(VarmapVisitor::<K, V>(marker::PhantomData))
+        }
+    }
+}
+
+/// Deserialize instance of a [`crate::Varuint`] holding a big integer, limited
+/// to [`crate::num_bigint::MAX_BIGUINT_LEN`] bytes
+#[cfg(feature = "num-bigint")]
+impl<'de> de::Deserialize<'de> for Varuint<num_bigint::BigUint> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use crate::{error::VaruintError, num_bigint::MAX_BIGUINT_LEN};
+        use num_bigint::BigUint;
+
+        struct BigVaruintVisitor;
+
+        impl<'de> de::Visitor<'de> for BigVaruintVisitor {
+            type Value = Varuint<BigUint>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "varuint encoded big integer")
+            }
+
+            // only binary
+
+            #[inline]
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Varuint::try_from(v).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+
+            // binary / human readable
+
+            // this typically only happens when there are bytes serialized into
+            // a human readable format.
+            #[inline]
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let mut v = Vec::new();
+                while let Some(b) = seq.next_element()? {
+                    // stop before buffering more than the decoder accepts
+                    if v.len() == MAX_BIGUINT_LEN {
+                        let e = VaruintError::TooLong {
+                            max_len: MAX_BIGUINT_LEN,
+                        };
+                        return Err(de::Error::custom(display_chain(&e)));
+                    }
+                    v.push(b);
+                }
+                Varuint::try_from(v.as_slice()).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+        }
+
+        deserializer.deserialize_bytes(BigVaruintVisitor)
+    }
+}
+
+/// Deserialize instance of [`crate::SecretVarbytes`] from a byte slice,
+/// zeroizing any intermediate buffers
+#[cfg(feature = "zeroize")]
+impl<'de> de::Deserialize<'de> for crate::SecretVarbytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use crate::SecretVarbytes;
+        use zeroize::Zeroizing;
+
+        struct SecretVarbytesVisitor;
+
+        impl<'de> de::Visitor<'de> for SecretVarbytesVisitor {
+            type Value = SecretVarbytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "varuint encoded len followed by bytes")
+            }
+
+            // only binary
+
+            #[inline]
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                SecretVarbytes::try_from(v).map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+
+            #[inline]
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let v = Zeroizing::new(v);
+                SecretVarbytes::try_from(v.as_slice())
+                    .map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+
+            // binary / human readable
+
+            // this typically only happens when there are bytes serialized into
+            // a human readable format.
+            #[inline]
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let mut v = Zeroizing::new(Vec::new());
+                while let Some(b) = seq.next_element()? {
+                    v.push(b);
+                }
+                SecretVarbytes::try_from(v.as_slice())
+                    .map_err(|e| de::Error::custom(display_chain(&e)))
+            }
+        }
+
+        deserializer.deserialize_bytes(SecretVarbytesVisitor)
+    }
+}
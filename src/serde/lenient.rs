@@ -0,0 +1,36 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! Serde adapter for [`BaseEncoded`] fields that decodes human readable strings
+//! with [`DecodeOptions::lenient`], e.g. to accept hand-edited config files.
+//! Use it with `#[serde(with = "multiutil::serde::lenient")]`. Serialization
+//! and binary formats are unchanged.
+use crate::{error::display_chain, BaseEncoded, BaseEncoder, DecodeOptions, EncodingInfo};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error as StdError;
+
+/// Serialize a [`BaseEncoded`] the same as its [`Serialize`] impl
+pub fn serialize<T, Enc, S>(v: &BaseEncoded<T, Enc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    BaseEncoded<T, Enc>: Serialize,
+    T: EncodingInfo,
+    Enc: BaseEncoder,
+    S: Serializer,
+{
+    v.serialize(serializer)
+}
+
+/// Deserialize a [`BaseEncoded`], normalizing human readable strings first
+pub fn deserialize<'de, T, Enc, D>(deserializer: D) -> Result<BaseEncoded<T, Enc>, D::Error>
+where
+    T: Deserialize<'de> + EncodingInfo + for<'a> TryFrom<&'a [u8]>,
+    for<'a> <T as TryFrom<&'a [u8]>>::Error: StdError + Send + Sync + 'static,
+    Enc: BaseEncoder,
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return BaseEncoded::deserialize(deserializer);
+    }
+    let s = String::deserialize(deserializer)?;
+    BaseEncoded::try_decode_with(&s, &DecodeOptions::lenient())
+        .map(|(v, _)| v)
+        .map_err(|e| de::Error::custom(display_chain(&e)))
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Idnetifier: Apache-2.0
 //! Serde (de)serialization for ['crate::prelude::Tagged'] wrapped objects
+pub mod base_name;
 mod de;
+pub mod lenient;
 mod ser;
 
 #[cfg(test)]
@@ -274,15 +276,208 @@ mod tests {
         assert_tokens(&v.readable(), &[Token::Str("f808080808080808001")]);
     }
 
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde::base_name")]
+        base: Base,
+    }
+
+    #[test]
+    fn test_base_name_adapter() {
+        let c = Config {
+            base: Base::Base58Btc,
+        };
+        assert_eq!(
+            "{\"base\":\"Base58Btc\"}".to_string(),
+            serde_json::to_string(&c).unwrap()
+        );
+        let c2: Config = serde_json::from_str("{\"base\":\"base58btc\"}").unwrap();
+        assert_eq!(c, c2);
+        assert!(serde_json::from_str::<Config>("{\"base\":\"base58\"}").is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Pasted {
+        #[serde(with = "crate::serde::lenient")]
+        v: EncodedVarbytes,
+    }
+
+    #[test]
+    fn test_lenient_adapter() {
+        let p: Pasted = serde_json::from_str("{\"v\":\" bAE\\naq \"}").unwrap();
+        assert_eq!(Base::Base32Lower, p.v.encoding());
+        assert_eq!(
+            "{\"v\":\"baeaq\"}".to_string(),
+            serde_json::to_string(&p).unwrap()
+        );
+        assert!(serde_json::from_str::<EncodedVarbytes>("\" bAE\\naq \"").is_err());
+
+        // binary formats are unchanged
+        let b = serde_cbor::to_vec(&p).unwrap();
+        assert_eq!(p, serde_cbor::from_slice(&b).unwrap());
+    }
+
+    #[test]
+    fn test_cached_base_encoded() {
+        type Detected = CachedBaseEncoded<Varbytes, DetectedEncoder>;
+        let v = Detected::try_from("0000000100000001").unwrap();
+        assert_tokens(&v.clone().readable(), &[Token::Str("0000000100000001")]);
+        let s = serde_json::to_string(&v).unwrap();
+        let v2: Detected = serde_json::from_str(&s).unwrap();
+        assert_eq!(Some("0000000100000001"), v2.original());
+
+        let b = serde_cbor::to_vec(&v).unwrap();
+        let v3: Detected = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(v, v3);
+        assert_eq!(None, v3.original());
+    }
+
+    #[test]
+    fn test_varuint_codec() {
+        assert_tokens(&Varuint(Codec::Ed25519Pub), &[Token::Bytes(&[0xed, 0x01])]);
+        // a CBOR byte string holding a varuint that is not a known codec
+        let b = hex::decode("44ffffff7f").unwrap();
+        let e = serde_cbor::from_slice::<Varuint<Codec>>(&b).unwrap_err();
+        assert!(e.to_string().contains("is not a known Codec"));
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_varuint_biguint() {
+        use num_bigint::BigUint;
+
+        let v = Varuint(BigUint::from(u128::MAX) + 1_u32);
+        let b = serde_cbor::to_vec(&v).unwrap();
+        assert_eq!(v, serde_cbor::from_slice(&b).unwrap());
+        let s = serde_json::to_string(&v).unwrap();
+        assert_eq!(v, serde_json::from_str(&s).unwrap());
+        assert_tokens(
+            &Varuint(BigUint::from(0xed_u32)),
+            &[Token::Bytes(&[0xed, 0x01])],
+        );
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_varuint_biguint_too_long() {
+        use crate::num_bigint::MAX_BIGUINT_LEN;
+        use num_bigint::BigUint;
+
+        // the sequence is rejected as soon as it is longer than the limit
+        let s = serde_json::to_string(&vec![0x80_u8; MAX_BIGUINT_LEN + 1]).unwrap();
+        let e = serde_json::from_str::<Varuint<BigUint>>(&s).unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("Varuint is longer than 1024 bytes"));
+    }
+
     #[test]
     fn test_varbytes() {
         let v = Varbytes(vec![0x01, 0x02, 0x03]);
         assert_tokens(&v, &[Token::Bytes(&[0x03, 0x01, 0x02, 0x03])]);
     }
 
+    #[test]
+    fn test_varbytes_truncated() {
+        // a CBOR byte string holding a varbytes that declares 4 bytes but has 2
+        let b = hex::decode("43040102").unwrap();
+        assert!(serde_cbor::from_slice::<Varbytes>(&b).is_err());
+    }
+
     #[test]
     fn test_encoded_varbytes() {
         let v = Varbytes::encoded_new(Base::Base16Lower, vec![0x01, 0x02, 0x03]);
         assert_tokens(&v.readable(), &[Token::Str("f03010203")]);
     }
+
+    #[test]
+    fn test_codec_payload() {
+        let cp = CodecPayload::new(Codec::Sha2256, vec![0x01, 0x02, 0x03]);
+        assert_tokens(&cp, &[Token::Bytes(&[0x12, 0x03, 0x01, 0x02, 0x03])]);
+    }
+
+    #[test]
+    fn test_codec_payload_length_mismatch() {
+        // a CBOR byte string holding a codec payload that declares 4 bytes but has 3
+        let b = hex::decode("451204010203").unwrap();
+        assert!(serde_cbor::from_slice::<CodecPayload>(&b).is_err());
+    }
+
+    #[test]
+    fn test_encoded_codec_payload() {
+        let cp1 = CodecPayload::encoded_new(Base::Base16Lower, Codec::Sha2256, vec![0x01, 0x02]);
+        assert_tokens(&cp1.clone().readable(), &[Token::Str("f12020102")]);
+        let s = serde_json::to_string(&cp1).unwrap();
+        let cp2: EncodedCodecPayload = serde_json::from_str(&s).unwrap();
+        assert_eq!(cp1, cp2);
+    }
+
+    fn attributes() -> Varmap {
+        let mut vm = Varmap::new();
+        vm.insert(0x10, vec![0x01, 0x02]);
+        vm.insert(0x01, vec![0xac, 0x02]);
+        vm
+    }
+
+    #[test]
+    fn test_varmap_json() {
+        let vm1 = attributes();
+        let s = serde_json::to_string(&vm1).unwrap();
+        assert_eq!("{\"1\":\"fac02\",\"16\":\"f0102\"}".to_string(), s);
+        let vm2: Varmap = serde_json::from_str(&s).unwrap();
+        assert_eq!(vm1, vm2);
+    }
+
+    #[test]
+    fn test_varmap_json_duplicate_key() {
+        let s = "{\"1\":\"fac02\",\"1\":\"f0102\"}";
+        assert!(serde_json::from_str::<Varmap>(s).is_err());
+    }
+
+    #[test]
+    fn test_varmap_cbor() {
+        let vm1 = attributes();
+        let b = serde_cbor::to_vec(&vm1).unwrap();
+        assert_eq!(hex::decode("49020102ac0210020102").unwrap(), b);
+        let vm2: Varmap = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(vm1, vm2);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_secret_varbytes() {
+        use crate::SecretVarbytes;
+        let s1 = SecretVarbytes::new(vec![0x01, 0x02, 0x03]);
+        assert_tokens(&s1, &[Token::Bytes(&[0x03, 0x01, 0x02, 0x03])]);
+        let b = serde_cbor::to_vec(&s1).unwrap();
+        let s2: SecretVarbytes = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(s1, s2);
+    }
+
+    #[test]
+    fn test_fixed_base_compact() {
+        use crate::base_marker::Base58Btc;
+
+        let v = FixedBaseEncoded::<Varbytes, Base58Btc>::from(Varbytes(vec![0x01]));
+        let b = serde_cbor::to_vec(&v).unwrap();
+        let v2: FixedBaseEncoded<Varbytes, Base58Btc> = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(v, v2);
+
+        // the same value tagged with another base is rejected
+        let b = serde_cbor::to_vec(&Varbytes::encoded_new(Base::Base16Lower, vec![0x01])).unwrap();
+        assert!(serde_cbor::from_slice::<FixedBaseEncoded<Varbytes, Base58Btc>>(&b).is_err());
+    }
+
+    #[test]
+    fn test_other_base_compact() {
+        // only fixed encoders reject a base other than their preferred one
+        let v = BaseEncoded::<Varbytes, Base58Encoder>::new(Base::Base16Lower, Varbytes(vec![1]));
+        let b = serde_cbor::to_vec(&v).unwrap();
+        let v2: BaseEncoded<Varbytes, Base58Encoder> = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(Base::Base16Lower, v2.encoding());
+
+        let b = serde_cbor::to_vec(&Varbytes::encoded_new(Base::Base16Lower, vec![1])).unwrap();
+        let v3: BaseEncoded<Varbytes, DetectedEncoder> = serde_cbor::from_slice(&b).unwrap();
+        assert_eq!(Base::Base16Lower, v3.encoding());
+    }
 }
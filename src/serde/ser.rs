@@ -1,7 +1,11 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::{BaseEncoded, BaseEncoder, EncodingInfo, Varbytes, Varuint};
+use crate::{
+    BaseEncoded, BaseEncoder, CachedBaseEncoded, CodecPayload, EncodingInfo, Varbytes, Varmap,
+    Varuint, VaruintRepr,
+};
+use multibase::Base;
 use multitrait::prelude::EncodeInto;
-use serde::ser;
+use serde::ser::{self, SerializeMap};
 
 /// Serialize instance of [`crate::BaseEncoded`] into
 impl<T, Enc> ser::Serialize for BaseEncoded<T, Enc>
@@ -21,16 +25,35 @@ where
     }
 }
 
+/// Serialize instance of [`crate::CachedBaseEncoded`], using the original
+/// string if there is one
+impl<T, Enc> ser::Serialize for CachedBaseEncoded<T, Enc>
+where
+    T: ser::Serialize + EncodingInfo + Clone + Into<Vec<u8>>,
+    Enc: BaseEncoder,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            self.as_str().serialize(serializer)
+        } else {
+            (self.encoding().code(), (**self).clone()).serialize(serializer)
+        }
+    }
+}
+
 /// Serialize instance of [`crate::Varuint`]
 impl<T> ser::Serialize for Varuint<T>
 where
-    T: EncodeInto,
+    T: VaruintRepr,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        serializer.serialize_bytes(self.0.encode_into().as_slice())
+        serializer.serialize_bytes(self.0.to_repr().encode_into().as_slice())
     }
 }
 
@@ -40,8 +63,65 @@ impl ser::Serialize for Varbytes {
     where
         S: ser::Serializer,
     {
-        let mut v = self.0.len().encode_into();
-        v.append(&mut self.0.clone());
-        serializer.serialize_bytes(v.as_slice())
+        serializer.serialize_bytes(self.encode_into().as_slice())
+    }
+}
+
+/// Serialize instance of [`crate::CodecPayload`]
+impl<P> ser::Serialize for CodecPayload<P>
+where
+    P: AsRef<[u8]>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(self.encode_into().as_slice())
+    }
+}
+
+/// Serialize instance of [`crate::Varmap`] as a map of keys to multibase
+/// encoded values when human readable and as its binary encoding otherwise
+impl<K, V> ser::Serialize for Varmap<K, V>
+where
+    K: ser::Serialize + EncodeInto,
+    V: AsRef<[u8]>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for (key, value) in self.iter() {
+                map.serialize_entry(key, &multibase::encode(Base::Base16Lower, value))?;
+            }
+            map.end()
+        } else {
+            serializer.serialize_bytes(self.encode_into().as_slice())
+        }
+    }
+}
+
+/// Serialize instance of a [`crate::Varuint`] holding a big integer
+#[cfg(feature = "num-bigint")]
+impl ser::Serialize for Varuint<num_bigint::BigUint> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(Vec::from(self.clone()).as_slice())
+    }
+}
+
+/// Serialize instance of [`crate::SecretVarbytes`] from a buffer that is
+/// zeroized after serializing
+#[cfg(feature = "zeroize")]
+impl ser::Serialize for crate::SecretVarbytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(self.encode_zeroizing().as_slice())
     }
 }
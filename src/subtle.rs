@@ -0,0 +1,107 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`ConstantTimeEq`] impls for comparing MACs, key ids and other secret
+//! dependent values without leaking where they differ through timing. The
+//! lengths of the values being compared are not considered secret, values of
+//! different lengths compare unequal in time that depends only on the lengths.
+//!
+//! ```
+//! use multiutil::Varbytes;
+//! use subtle::ConstantTimeEq;
+//!
+//! let mac = Varbytes(vec![0xde, 0xad, 0xbe, 0xef]);
+//! let expected = Varbytes(vec![0xde, 0xad, 0xbe, 0xef]);
+//! assert!(bool::from(mac.ct_eq(&expected)));
+//! ```
+use crate::{BaseEncoded, BaseEncoder, CodecInfo, CodecPayload, EncodingInfo, Varbytes, Varuint};
+use subtle::{Choice, ConstantTimeEq};
+
+impl ConstantTimeEq for Varbytes {
+    /// compare the bytes in constant time
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_slice().ct_eq(other.0.as_slice())
+    }
+}
+
+impl<T> ConstantTimeEq for Varuint<T>
+where
+    T: ConstantTimeEq,
+{
+    /// compare the values in constant time
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<P> ConstantTimeEq for CodecPayload<P>
+where
+    P: AsRef<[u8]>,
+{
+    /// compare the codecs and the payload bytes in constant time. this works
+    /// for fixed size payloads such as `[u8; 32]` as well as for `Vec<u8>`.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.codec().code().ct_eq(&other.codec().code())
+            & self.payload().as_ref().ct_eq(other.payload().as_ref())
+    }
+}
+
+impl<T, Enc> ConstantTimeEq for BaseEncoded<T, Enc>
+where
+    T: ConstantTimeEq + EncodingInfo,
+    Enc: BaseEncoder,
+{
+    /// compare the bases and the inner values in constant time
+    fn ct_eq(&self, other: &Self) -> Choice {
+        u32::from(self.base.code()).ct_eq(&u32::from(other.base.code())) & self.t.ct_eq(&other.t)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ConstantTimeEq for crate::SecretVarbytes {
+    /// compare the secret bytes in constant time
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.expose_secret().ct_eq(other.expose_secret())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Base, Codec};
+
+    #[test]
+    fn test_varbytes() {
+        let a = Varbytes(vec![1, 2, 3]);
+        assert!(bool::from(a.ct_eq(&Varbytes(vec![1, 2, 3]))));
+        assert!(!bool::from(a.ct_eq(&Varbytes(vec![1, 2, 4]))));
+        assert!(!bool::from(a.ct_eq(&Varbytes(vec![1, 2]))));
+    }
+
+    #[test]
+    fn test_fixed_codec_payload() {
+        let a = CodecPayload::new(Codec::Sha2256, [0xaa; 32]);
+        assert!(bool::from(
+            a.ct_eq(&CodecPayload::new(Codec::Sha2256, [0xaa; 32]))
+        ));
+        assert!(!bool::from(
+            a.ct_eq(&CodecPayload::new(Codec::Sha2512, [0xaa; 32]))
+        ));
+        assert!(!bool::from(
+            a.ct_eq(&CodecPayload::new(Codec::Sha2256, [0xbb; 32]))
+        ));
+    }
+
+    #[test]
+    fn test_base_encoded() {
+        let a = Varbytes::encoded_new(Base::Base58Btc, vec![1, 2, 3]);
+        assert!(bool::from(
+            a.ct_eq(&Varbytes::encoded_new(Base::Base58Btc, vec![1, 2, 3]))
+        ));
+        assert!(!bool::from(a.ct_eq(&Varbytes::encoded_new(
+            Base::Base16Lower,
+            vec![1, 2, 3]
+        ))));
+        assert!(!bool::from(
+            a.ct_eq(&Varbytes::encoded_new(Base::Base58Btc, vec![3, 2, 1]))
+        ));
+    }
+}
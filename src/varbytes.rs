@@ -1,5 +1,8 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::{BaseEncoded, EncodingInfo, Error};
+use crate::{
+    decode_usize, encoded_len::check_len, error::VarbytesError, BaseEncoded, EncodedLen,
+    EncodingInfo, Error, Varuint,
+};
 use core::{fmt, ops};
 use multibase::Base;
 use multitrait::prelude::{EncodeInto, TryDecodeFrom};
@@ -56,12 +59,26 @@ impl From<Varbytes> for Vec<u8> {
 
 impl EncodeInto for Varbytes {
     fn encode_into(&self) -> Vec<u8> {
-        let mut v = self.0.len().encode_into();
-        v.append(&mut self.0.clone());
+        let mut v = Vec::with_capacity(EncodedLen::encoded_len(self));
+        self.encode_extend(&mut v);
         v
     }
 }
 
+impl EncodedLen for Varbytes {
+    fn encoded_len(&self) -> usize {
+        Varuint(self.0.len()).encoded_len() + self.0.len()
+    }
+
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        check_len(buf, EncodedLen::encoded_len(self))?;
+        let prefix = Varuint(self.0.len()).encode_to_slice(buf)?;
+        let len = prefix + self.0.len();
+        buf[prefix..len].copy_from_slice(&self.0);
+        Ok(len)
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for Varbytes {
     type Error = Error;
 
@@ -75,7 +92,14 @@ impl<'a> TryDecodeFrom<'a> for Varbytes {
     type Error = Error;
 
     fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
-        let (len, ptr) = usize::try_decode_from(bytes)?;
+        let (len, ptr) = decode_usize(bytes)?;
+        if ptr.len() < len {
+            return Err(VarbytesError::Truncated {
+                needed: len,
+                available: ptr.len(),
+            }
+            .into());
+        }
         let v = ptr[..len].to_vec();
         let ptr = &ptr[len..];
         Ok((Self(v), ptr))
@@ -121,6 +145,30 @@ mod test {
         assert_eq!(v1, v2);
     }
 
+    #[test]
+    fn test_truncated() {
+        assert!(Varbytes::try_decode_from(&[4, 1, 2, 3]).is_err());
+        assert!(Varbytes::try_from([0xff, 0xff, 0xff, 0xff, 0x0f].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        let v = Varbytes(vec![1, 2, 3]);
+        let mut buf = [0u8; 8];
+        assert_eq!(4, v.encoded_len());
+        assert_eq!(4, v.encode_to_slice(&mut buf).unwrap());
+        assert_eq!(&[3, 1, 2, 3], &buf[..4]);
+        assert!(v.encode_to_slice(&mut buf[..3]).is_err());
+    }
+
+    #[test]
+    fn test_encode_extend() {
+        let v = Varbytes(vec![0xaa; 200]);
+        let mut buf = Vec::new();
+        v.encode_extend(&mut buf);
+        assert_eq!(v.encode_into(), buf);
+    }
+
     #[test]
     fn test_debug() {
         let v = Varbytes(vec![1, 2, 3]);
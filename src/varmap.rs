@@ -0,0 +1,392 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    decode_usize,
+    encoded_len::check_len,
+    error::{VarbytesError, VarmapError},
+    BaseEncoded, EncodedLen, EncodingInfo, Error, Varuint, VaruintRepr,
+};
+use core::{fmt, ops};
+use multibase::Base;
+use multitrait::prelude::{EncodeInto, TryDecodeFrom};
+use std::{
+    collections::{btree_map, BTreeMap},
+    error::Error as StdError,
+    sync::Arc,
+};
+
+/// A map of varuint keys to length-prefixed values. The canonical encoding is
+/// sorted by key and has no duplicate keys:
+///
+/// ```text
+/// <varmap> ::= <varuint count> N(<varuint key> <varbytes value>)
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Varmap<K = u64, V = Vec<u8>>(BTreeMap<K, V>);
+
+/// type alias for a Varmap base encoded to/from string
+pub type EncodedVarmap<K = u64, V = Vec<u8>> = BaseEncoded<Varmap<K, V>>;
+
+impl<K, V> Varmap<K, V>
+where
+    K: Ord,
+{
+    /// create an empty varmap
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// create an encoded varmap
+    pub fn encoded_new(base: Base, map: BTreeMap<K, V>) -> EncodedVarmap<K, V> {
+        BaseEncoded::new(base, Self(map))
+    }
+
+    /// insert a value, returning the value it replaced if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.0.insert(key, value)
+    }
+
+    /// remove a value, returning it if it was present
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.0.remove(key)
+    }
+
+    /// consume self and return inner map
+    pub fn to_inner(self) -> BTreeMap<K, V> {
+        self.0
+    }
+}
+
+impl<K, V> Varmap<K, V>
+where
+    K: Ord + fmt::Debug,
+    V: AsRef<[u8]>,
+{
+    /// get the value for the key decoded as a T
+    pub fn get_as<'a, T>(&'a self, key: &K) -> Result<Option<T>, Error>
+    where
+        T: TryFrom<&'a [u8]>,
+        T::Error: StdError + Send + Sync + 'static,
+    {
+        match self.0.get(key) {
+            Some(v) => T::try_from(v.as_ref())
+                .map(Some)
+                .map_err(|e| value_failed(key, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// get the value for the key decoded as a varuint
+    pub fn get_varuint<T>(&self, key: &K) -> Result<Option<T>, Error>
+    where
+        T: VaruintRepr,
+    {
+        Ok(self.get_as::<Varuint<T>>(key)?.map(|v| v.0))
+    }
+}
+
+fn value_failed<K, E>(key: &K, e: E) -> Error
+where
+    K: fmt::Debug,
+    E: StdError + Send + Sync + 'static,
+{
+    VarmapError::ValueFailed {
+        key: format!("{:?}", key),
+        source: Arc::new(e),
+    }
+    .into()
+}
+
+impl<K, V> Default for Varmap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for Varmap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl<K, V> ops::Deref for Varmap<K, V> {
+    type Target = BTreeMap<K, V>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for Varmap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self(map)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for Varmap<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(BTreeMap::from_iter(iter))
+    }
+}
+
+impl<K, V> IntoIterator for Varmap<K, V> {
+    type Item = (K, V);
+    type IntoIter = btree_map::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<K, V> EncodingInfo for Varmap<K, V> {
+    fn preferred_encoding() -> Base {
+        Base::Base16Lower
+    }
+
+    fn encoding(&self) -> Base {
+        Base::Base16Lower
+    }
+}
+
+impl<K, V> From<Varmap<K, V>> for Vec<u8>
+where
+    K: EncodeInto,
+    V: AsRef<[u8]>,
+{
+    fn from(vm: Varmap<K, V>) -> Vec<u8> {
+        vm.encode_into()
+    }
+}
+
+impl<K, V> EncodeInto for Varmap<K, V>
+where
+    K: EncodeInto,
+    V: AsRef<[u8]>,
+{
+    fn encode_into(&self) -> Vec<u8> {
+        let mut v = self.0.len().encode_into();
+        for (key, value) in &self.0 {
+            let value = value.as_ref();
+            v.append(&mut key.encode_into());
+            v.append(&mut value.len().encode_into());
+            v.extend_from_slice(value);
+        }
+        v
+    }
+}
+
+impl<K, V> EncodedLen for Varmap<K, V>
+where
+    K: Copy,
+    Varuint<K>: EncodedLen,
+    V: AsRef<[u8]>,
+{
+    fn encoded_len(&self) -> usize {
+        self.0
+            .iter()
+            .fold(Varuint(self.0.len()).encoded_len(), |len, (key, value)| {
+                let value = value.as_ref().len();
+                len + Varuint(*key).encoded_len() + Varuint(value).encoded_len() + value
+            })
+    }
+
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        check_len(buf, self.encoded_len())?;
+        let mut i = Varuint(self.0.len()).encode_to_slice(buf)?;
+        for (key, value) in &self.0 {
+            let value = value.as_ref();
+            i += Varuint(*key).encode_to_slice(&mut buf[i..])?;
+            i += Varuint(value.len()).encode_to_slice(&mut buf[i..])?;
+            buf[i..i + value.len()].copy_from_slice(value);
+            i += value.len();
+        }
+        Ok(i)
+    }
+}
+
+impl<'a, K, V> TryFrom<&'a [u8]> for Varmap<K, V>
+where
+    K: Ord + fmt::Debug + TryDecodeFrom<'a>,
+    K::Error: StdError + Send + Sync + 'static,
+    V: TryFrom<&'a [u8]>,
+    V::Error: StdError + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    fn try_from(s: &'a [u8]) -> Result<Self, Error> {
+        let (v, _) = Self::try_decode_from(s)?;
+        Ok(v)
+    }
+}
+
+impl<'a, K, V> TryDecodeFrom<'a> for Varmap<K, V>
+where
+    K: Ord + fmt::Debug + TryDecodeFrom<'a>,
+    K::Error: StdError + Send + Sync + 'static,
+    V: TryFrom<&'a [u8]>,
+    V::Error: StdError + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    /// decode a varmap, rejecting duplicate and unsorted keys so that only the
+    /// canonical encoding is accepted
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (count, mut ptr) = decode_usize(bytes)?;
+        let mut map = BTreeMap::new();
+        for index in 0..count {
+            let (key, p) = K::try_decode_from(ptr).map_err(|e| VarmapError::KeyFailed {
+                index,
+                source: Arc::new(e),
+            })?;
+            if let Some((previous, _)) = map.last_key_value() {
+                if key == *previous {
+                    return Err(VarmapError::DuplicateKey {
+                        key: format!("{:?}", key),
+                    }
+                    .into());
+                }
+                if key < *previous {
+                    return Err(VarmapError::UnsortedKeys {
+                        previous: format!("{:?}", previous),
+                        key: format!("{:?}", key),
+                    }
+                    .into());
+                }
+            }
+            let (len, p) = decode_usize(p)?;
+            if p.len() < len {
+                return Err(VarbytesError::Truncated {
+                    needed: len,
+                    available: p.len(),
+                }
+                .into());
+            }
+            let value = V::try_from(&p[..len]).map_err(|e| value_failed(&key, e))?;
+            map.insert(key, value);
+            ptr = &p[len..];
+        }
+        Ok((Self(map), ptr))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn attributes() -> Varmap {
+        let mut vm = Varmap::new();
+        vm.insert(0x10, vec![0x01, 0x02]);
+        vm.insert(0x01, 300_u64.encode_into());
+        vm.insert(0x80, Vec::new());
+        vm
+    }
+
+    #[test]
+    fn test_canonical_encoding() {
+        let vm = attributes();
+        assert_eq!(
+            vec![0x03, 0x01, 0x02, 0xac, 0x02, 0x10, 0x02, 0x01, 0x02, 0x80, 0x01, 0x00],
+            vm.encode_into()
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let vm1 = attributes();
+        let b = vm1.encode_into();
+        let (vm2, ptr) = Varmap::<u64, Vec<u8>>::try_decode_from(&b).unwrap();
+        assert_eq!(vm1, vm2);
+        assert!(ptr.is_empty());
+    }
+
+    #[test]
+    fn test_unsorted_keys() {
+        let vm: Varmap = [(0x01, vec![0xff]), (0x10, vec![0x01, 0x02])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            vec![0x02, 0x01, 0x01, 0xff, 0x10, 0x02, 0x01, 0x02],
+            vm.encode_into()
+        );
+        // the canonical encoding with the pairs reversed
+        let reversed = vec![0x02, 0x10, 0x02, 0x01, 0x02, 0x01, 0x01, 0xff];
+        match Varmap::<u64, Vec<u8>>::try_from(reversed.as_slice()) {
+            Err(Error::Varmap(VarmapError::UnsortedKeys { previous, key })) => {
+                assert_eq!("16", previous);
+                assert_eq!("1", key);
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key() {
+        let b = vec![0x02, 0x01, 0x01, 0xaa, 0x01, 0x01, 0xbb];
+        match Varmap::<u64, Vec<u8>>::try_from(b.as_slice()) {
+            Err(Error::Varmap(VarmapError::DuplicateKey { key })) => assert_eq!("1", key),
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let b = vec![0x02, 0x01, 0x01, 0xaa, 0x02, 0x04, 0xbb];
+        match Varmap::<u64, Vec<u8>>::try_from(b.as_slice()).unwrap_err() {
+            Error::Varbytes(VarbytesError::Truncated { needed, available }) => {
+                assert_eq!(4, needed);
+                assert_eq!(1, available);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_key_failed() {
+        // the second key is truncated
+        let b = vec![0x02, 0x01, 0x01, 0xaa, 0x82];
+        match Varmap::<u64, Vec<u8>>::try_from(b.as_slice()).unwrap_err() {
+            Error::Varmap(VarmapError::KeyFailed { index, source }) => {
+                assert_eq!(1, index);
+                assert!(source.downcast_ref::<multitrait::Error>().is_some());
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let vm = attributes();
+        assert_eq!(Some(300), vm.get_varuint::<u64>(&0x01).unwrap());
+        assert_eq!(None, vm.get_varuint::<u64>(&0x02).unwrap());
+        assert_eq!(Some([0x01, 0x02]), vm.get_as::<[u8; 2]>(&0x10).unwrap());
+        assert!(vm.get_as::<[u8; 4]>(&0x10).is_err());
+        assert!(vm.get_varuint::<u64>(&0x80).is_err());
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let vm = attributes();
+        let mut buf = Vec::new();
+        vm.encode_extend(&mut buf);
+        assert_eq!(vm.encode_into(), buf);
+        assert_eq!(buf.len(), vm.encoded_len());
+    }
+
+    #[test]
+    fn test_base_encoded_round_trip() {
+        let evm1 = Varmap::encoded_new(Base::Base32Lower, attributes().to_inner());
+        let s = evm1.to_string();
+        let evm2 = EncodedVarmap::try_from(s.as_str()).unwrap();
+        assert_eq!(evm1, evm2);
+    }
+}
@@ -1,11 +1,15 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::{BaseEncoded, EncodingInfo, Error};
-use core::{fmt, ops};
+use crate::{
+    encoded_len::check_len, error::VaruintError, BaseEncoded, EncodedLen, EncodingInfo, Error,
+    VaruintRepr,
+};
+use core::{fmt, ops, str::FromStr};
 use multibase::Base;
 use multitrait::{EncodeInto, TryDecodeFrom};
 
-/// A wrapper type to handle serde of numeric types as varuint bytes
-#[derive(Clone, PartialEq)]
+/// A wrapper type to handle serde of numeric types, and other types with a
+/// [`VaruintRepr`], as varuint bytes
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Varuint<T>(pub T);
 
 /// type alias for a Varuint base encoded to/from string
@@ -13,7 +17,7 @@ pub type EncodedVaruint<T> = BaseEncoded<Varuint<T>>;
 
 impl<T> Varuint<T>
 where
-    T: EncodeInto + for<'a> TryDecodeFrom<'a>,
+    T: VaruintRepr,
 {
     /// create a new encoded varuint
     pub fn encoded_new(base: Base, t: T) -> EncodedVaruint<T> {
@@ -35,12 +39,32 @@ where
     }
 }
 
+impl<T> FromStr for Varuint<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(T::from_str(s)?))
+    }
+}
+
+impl<T> fmt::Display for Varuint<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
 impl<T> fmt::Debug for Varuint<T>
 where
-    T: EncodeInto,
+    T: VaruintRepr,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.0.encode_into().as_slice())
+        write!(f, "{:?}", self.0.to_repr().encode_into().as_slice())
     }
 }
 
@@ -53,6 +77,167 @@ impl<T> ops::Deref for Varuint<T> {
     }
 }
 
+macro_rules! impl_varuint_op {
+    ($($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident;)*) => {
+        $(
+            impl<T> ops::$op for Varuint<T>
+            where
+                T: ops::$op<Output = T>,
+            {
+                type Output = Self;
+
+                fn $fn(self, rhs: Self) -> Self {
+                    Self(ops::$op::$fn(self.0, rhs.0))
+                }
+            }
+
+            impl<T> ops::$op_assign for Varuint<T>
+            where
+                T: ops::$op_assign,
+            {
+                fn $fn_assign(&mut self, rhs: Self) {
+                    ops::$op_assign::$fn_assign(&mut self.0, rhs.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_varuint_op! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+    Rem, rem, RemAssign, rem_assign;
+    BitAnd, bitand, BitAndAssign, bitand_assign;
+    BitOr, bitor, BitOrAssign, bitor_assign;
+    BitXor, bitxor, BitXorAssign, bitxor_assign;
+}
+
+macro_rules! impl_varuint_width {
+    ($($t:ty),*) => {
+        $(
+            impl Varuint<$t> {
+                /// the number of bytes in the varuint encoding of this value,
+                /// computed without allocating
+                pub const fn encoded_len(&self) -> usize {
+                    let bits = <$t>::BITS - self.0.leading_zeros();
+                    if bits == 0 {
+                        1
+                    } else {
+                        bits.div_ceil(7) as usize
+                    }
+                }
+
+                /// checked addition, returns None on overflow
+                pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                    self.0.checked_add(rhs.0).map(Self)
+                }
+
+                /// checked subtraction, returns None on underflow
+                pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    self.0.checked_sub(rhs.0).map(Self)
+                }
+
+                /// checked multiplication, returns None on overflow
+                pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    self.0.checked_mul(rhs.0).map(Self)
+                }
+
+                /// checked division, returns None if rhs is zero
+                pub fn checked_div(self, rhs: Self) -> Option<Self> {
+                    self.0.checked_div(rhs.0).map(Self)
+                }
+
+                /// checked remainder, returns None if rhs is zero
+                pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    self.0.checked_rem(rhs.0).map(Self)
+                }
+            }
+
+            impl EncodedLen for Varuint<$t> {
+                fn encoded_len(&self) -> usize {
+                    Varuint::<$t>::encoded_len(self)
+                }
+
+                fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+                    let len = Varuint::<$t>::encoded_len(self);
+                    check_len(buf, len)?;
+                    let mut n = self.0;
+                    for b in buf[..len - 1].iter_mut() {
+                        *b = n.to_le_bytes()[0] | 0x80;
+                        n >>= 7;
+                    }
+                    buf[len - 1] = n.to_le_bytes()[0];
+                    Ok(len)
+                }
+            }
+
+            impl From<$t> for Varuint<$t> {
+                fn from(t: $t) -> Self {
+                    Self(t)
+                }
+            }
+
+            impl From<Varuint<$t>> for $t {
+                fn from(vu: Varuint<$t>) -> $t {
+                    vu.0
+                }
+            }
+        )*
+    };
+}
+
+impl_varuint_width!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_varuint_widen {
+    ($($from:ty => $($to:ty),*;)*) => {
+        $($(
+            impl From<Varuint<$from>> for Varuint<$to> {
+                fn from(vu: Varuint<$from>) -> Self {
+                    Self(<$to>::from(vu.0))
+                }
+            }
+        )*)*
+    };
+}
+
+impl_varuint_widen! {
+    u8 => u16, u32, u64, u128, usize;
+    u16 => u32, u64, u128, usize;
+    u32 => u64, u128;
+    u64 => u128;
+}
+
+macro_rules! impl_varuint_narrow {
+    ($($from:ty => $($to:ty),*;)*) => {
+        $($(
+            impl TryFrom<Varuint<$from>> for Varuint<$to> {
+                type Error = Error;
+
+                fn try_from(vu: Varuint<$from>) -> Result<Self, Self::Error> {
+                    match <$to>::try_from(vu.0) {
+                        Ok(t) => Ok(Self(t)),
+                        Err(_) => Err(VaruintError::Overflow {
+                            value: vu.0.to_string(),
+                            target: stringify!($to),
+                        }
+                        .into()),
+                    }
+                }
+            }
+        )*)*
+    };
+}
+
+impl_varuint_narrow! {
+    u16 => u8;
+    u32 => u8, u16, usize;
+    u64 => u8, u16, u32, usize;
+    u128 => u8, u16, u32, u64, usize;
+    usize => u8, u16, u32, u64, u128;
+}
+
 impl<T> EncodingInfo for Varuint<T> {
     fn preferred_encoding() -> Base {
         Base::Base16Lower
@@ -65,16 +250,16 @@ impl<T> EncodingInfo for Varuint<T> {
 
 impl<T> From<Varuint<T>> for Vec<u8>
 where
-    T: EncodeInto,
+    T: VaruintRepr,
 {
     fn from(vu: Varuint<T>) -> Vec<u8> {
-        vu.0.encode_into()
+        vu.0.to_repr().encode_into()
     }
 }
 
 impl<'a, T> TryFrom<&'a [u8]> for Varuint<T>
 where
-    T: TryDecodeFrom<'a>,
+    T: VaruintRepr,
 {
     type Error = Error;
 
@@ -86,14 +271,13 @@ where
 
 impl<'a, T> TryDecodeFrom<'a> for Varuint<T>
 where
-    T: TryDecodeFrom<'a>,
+    T: VaruintRepr,
 {
     type Error = Error;
 
     fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
-        let (t, ptr) =
-            T::try_decode_from(bytes).map_err(|_| Error::custom("failed to decode varuint"))?;
-        Ok((Self(t), ptr))
+        let (repr, ptr) = T::try_decode_repr(bytes)?;
+        Ok((Self(T::from_repr(repr)?), ptr))
     }
 }
 
@@ -116,7 +300,7 @@ mod test {
     #[test]
     fn test_default_round_trip() {
         let v1 = Varuint::<u64>::default();
-        let v: Vec<u8> = v1.clone().into();
+        let v: Vec<u8> = v1.into();
         let v2 = Varuint::<u64>::try_from(v.as_slice()).unwrap();
         assert_eq!(v1, v2);
     }
@@ -131,7 +315,7 @@ mod test {
     #[test]
     fn test_into_tryfrom_round_trip() {
         let v1 = Varuint(42_u64);
-        let data: Vec<u8> = v1.clone().into();
+        let data: Vec<u8> = v1.into();
         let v2 = Varuint::<u64>::try_from(data.as_slice()).unwrap();
         assert_eq!(v1, v2);
     }
@@ -141,4 +325,92 @@ mod test {
         let v = Varuint(0xed_u64);
         assert_eq!("[237, 1]".to_string(), format!("{:?}", v));
     }
+
+    #[test]
+    fn test_display() {
+        let v = Varuint(0xed_u64);
+        assert_eq!("237".to_string(), v.to_string());
+        assert_eq!(v, "237".parse().unwrap());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut v = Varuint(40_u64) + Varuint(2);
+        assert_eq!(Varuint(42_u64), v);
+        v -= Varuint(2);
+        assert_eq!(Varuint(40_u64), v);
+        assert_eq!(Varuint(80_u64), v * Varuint(2));
+        assert_eq!(Varuint(0_u64), v % Varuint(8));
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(
+            Some(Varuint(255_u8)),
+            Varuint(254_u8).checked_add(Varuint(1))
+        );
+        assert_eq!(None, Varuint(255_u8).checked_add(Varuint(1)));
+        assert_eq!(None, Varuint(0_u32).checked_sub(Varuint(1)));
+        assert_eq!(None, Varuint(1_u64).checked_div(Varuint(0)));
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Varuint(1_u16) < Varuint(2_u16));
+        assert_eq!(
+            Some(&Varuint(9_u16)),
+            [Varuint(9_u16), Varuint(3)].iter().max()
+        );
+    }
+
+    #[test]
+    fn test_from_inner() {
+        let v: Varuint<u64> = 42_u64.into();
+        assert_eq!(42_u64, u64::from(v));
+    }
+
+    #[test]
+    fn test_widen() {
+        let v: Varuint<u64> = Varuint(42_u32).into();
+        assert_eq!(Varuint(42_u64), v);
+    }
+
+    #[test]
+    fn test_narrow() {
+        let v = Varuint::<u32>::try_from(Varuint(42_u64)).unwrap();
+        assert_eq!(Varuint(42_u32), v);
+        assert!(Varuint::<u32>::try_from(Varuint(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        for n in [0_u64, 1, 0x7f, 0x80, 0x3fff, 0x4000, u64::MAX] {
+            let v = Varuint(n);
+            assert_eq!(v.encode_into().len(), v.encoded_len());
+        }
+        assert_eq!(19, Varuint(u128::MAX).encoded_len());
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        for n in [0_u64, 1, 0x7f, 0x80, 0x3fff, 0x4000, u64::MAX] {
+            let v = Varuint(n);
+            let mut buf = [0u8; 10];
+            let len = v.encode_to_slice(&mut buf).unwrap();
+            assert_eq!(v.encode_into().as_slice(), &buf[..len]);
+        }
+    }
+
+    #[test]
+    fn test_encode_to_slice_too_small() {
+        let mut buf = [0u8; 1];
+        assert!(Varuint(0x80_u16).encode_to_slice(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_encode_extend() {
+        let mut v = vec![0xff];
+        Varuint(0x3fff_u32).encode_extend(&mut v);
+        assert_eq!(vec![0xff, 0xff, 0x7f], v);
+    }
 }
@@ -0,0 +1,233 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! Word-at-a-time varuint decoding. One and two byte values are decoded
+//! directly. Otherwise, when at least 8 bytes are available they are loaded as
+//! one little-endian word, the terminating byte is found from the continuation
+//! bits and the 7-bit groups are packed together with shifts and masks.
+//! Anything the fast path can't handle (encodings longer than 8 bytes,
+//! non-minimal encodings and longer values near the end of the input) falls
+//! back to the scalar [`TryDecodeFrom`] decoder so the results, including the
+//! errors, are identical. Both reject a 10 byte varuint with bits beyond 64,
+//! which the scalar decoder alone would silently drop.
+use crate::{error::VaruintError, Error};
+use multitrait::TryDecodeFrom;
+
+/// the continuation bit of every byte in a word
+const CONTINUATION: u64 = 0x8080_8080_8080_8080;
+
+/// decode a varuint u64 from the front of the slice, returning the remaining
+/// bytes
+#[inline]
+pub fn decode_u64(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+    match decode_word(bytes) {
+        Some((n, len)) => Ok((n, &bytes[len..])),
+        None => decode_scalar(bytes),
+    }
+}
+
+/// decode a varuint usize from the front of the slice, returning the remaining
+/// bytes
+#[inline]
+pub fn decode_usize(bytes: &[u8]) -> Result<(usize, &[u8]), Error> {
+    let (n, ptr) = decode_u64(bytes)?;
+    match usize::try_from(n) {
+        Ok(n) => Ok((n, ptr)),
+        Err(_) => Err(VaruintError::Overflow {
+            value: n.to_string(),
+            target: "usize",
+        }
+        .into()),
+    }
+}
+
+/// decode a varuint u64 with the scalar decoder, rejecting a 10th byte with
+/// bits that don't fit in a u64 instead of dropping them
+#[cold]
+fn decode_scalar(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+    let (n, ptr) = u64::try_decode_from(bytes)?;
+    // the 10th byte holds bit 63 only
+    if bytes.len() - ptr.len() == 10 && bytes[9] > 0x01 {
+        let value = u128::from(n & (u64::MAX >> 1)) | u128::from(bytes[9]) << 63;
+        return Err(VaruintError::Overflow {
+            value: value.to_string(),
+            target: "u64",
+        }
+        .into());
+    }
+    Ok((n, ptr))
+}
+
+/// decode a minimally encoded varuint of at most 8 bytes from the front of the
+/// slice, returning the value and the encoded length
+#[inline(always)]
+fn decode_word(bytes: &[u8]) -> Option<(u64, usize)> {
+    // one and two byte values are the most common, e.g. short length
+    // prefixes, and predictable branches beat the word arithmetic for them
+    match *bytes {
+        [b0, ..] if b0 < 0x80 => return Some((u64::from(b0), 1)),
+        [b0, b1, ..] if b1 < 0x80 && b1 != 0 => {
+            return Some((u64::from(b0 & 0x7f) | u64::from(b1) << 7, 2))
+        }
+        _ => {}
+    }
+    let word = u64::from_le_bytes(bytes.get(..8)?.try_into().ok()?);
+
+    // the lowest byte with a clear continuation bit ends the varuint
+    let ends = !word & CONTINUATION;
+    if ends == 0 {
+        return None;
+    }
+    let len = (ends.trailing_zeros() / 8 + 1) as usize;
+
+    // a zero final byte is not minimal, let the scalar decoder decide
+    if len > 1 && bytes[len - 1] == 0 {
+        return None;
+    }
+
+    // drop the bytes past the end and the continuation bits, then pack the
+    // 7-bit groups into 14, 28 and finally 56 contiguous bits
+    let n = (word & (u64::MAX >> (64 - 8 * len))) & !CONTINUATION;
+    let n = (n & 0x007f_007f_007f_007f) | ((n & 0x7f00_7f00_7f00_7f00) >> 1);
+    let n = (n & 0x0000_3fff_0000_3fff) | ((n & 0x3fff_0000_3fff_0000) >> 2);
+    let n = (n & 0x0000_0000_0fff_ffff) | ((n & 0x0fff_ffff_0000_0000) >> 4);
+    Some((n, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multitrait::EncodeInto;
+
+    // a small deterministic generator so the differential tests are repeatable
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn scalar_u64(bytes: &[u8]) -> Result<(u64, &[u8]), String> {
+        u64::try_decode_from(bytes).map_err(|e| Error::from(e).to_string())
+    }
+
+    fn scalar_usize(bytes: &[u8]) -> Result<(usize, &[u8]), String> {
+        usize::try_decode_from(bytes).map_err(|e| Error::from(e).to_string())
+    }
+
+    // a terminated 10 byte varuint with bits beyond 64
+    fn overflows(bytes: &[u8]) -> bool {
+        bytes.len() >= 10
+            && bytes[..9].iter().all(|b| b & 0x80 != 0)
+            && (0x02..0x80).contains(&bytes[9])
+    }
+
+    fn assert_same(bytes: &[u8]) {
+        // the scalar decoder drops the overflowing bits so it isn't a reference
+        if overflows(bytes) {
+            assert!(matches!(
+                decode_u64(bytes),
+                Err(Error::Varuint(VaruintError::Overflow { .. }))
+            ));
+            assert!(matches!(
+                decode_usize(bytes),
+                Err(Error::Varuint(VaruintError::Overflow { .. }))
+            ));
+            return;
+        }
+        assert_eq!(
+            scalar_u64(bytes),
+            decode_u64(bytes).map_err(|e| e.to_string()),
+            "u64 {:02x?}",
+            bytes
+        );
+        assert_eq!(
+            scalar_usize(bytes),
+            decode_usize(bytes).map_err(|e| e.to_string()),
+            "usize {:02x?}",
+            bytes
+        );
+    }
+
+    #[test]
+    fn test_widths() {
+        for shift in 0..64 {
+            for n in [1_u64 << shift, (1_u64 << shift) - 1, u64::MAX >> shift] {
+                let mut b = n.encode_into();
+                let len = b.len();
+                b.extend_from_slice(&[0xff; 12]);
+                let (v, rest) = decode_u64(&b).unwrap();
+                assert_eq!(n, v);
+                assert_eq!(12, rest.len());
+                assert_same(&b);
+                assert_same(&b[..len]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_and_double_bytes() {
+        for b0 in 0..=0xff_u8 {
+            for b1 in 0..=0xff_u8 {
+                let mut b = [0x11_u8; 10];
+                b[0] = b0;
+                b[1] = b1;
+                assert_same(&b);
+                assert_same(&b[..2]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        // non-minimal final bytes at every position
+        for len in 2..=10 {
+            let mut b = vec![0x80_u8; len - 1];
+            b.push(0);
+            b.extend_from_slice(&[0x01; 8]);
+            assert_same(&b);
+        }
+        // unterminated, overlong and overflowing
+        assert_same(&[0xff; 8]);
+        assert_same(&[0xff; 16]);
+        assert_same(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]);
+        assert_same(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        assert_same(&[]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut b = vec![0x80_u8; 9];
+        b.push(0x02);
+        match decode_u64(&b).unwrap_err() {
+            Error::Varuint(VaruintError::Overflow { value, target }) => {
+                assert_eq!("18446744073709551616", value);
+                assert_eq!("u64", target);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(decode_usize(&b).is_err());
+        assert!(crate::Varbytes::try_decode_from(&b).is_err());
+        assert!(crate::Varuint::<u64>::try_from(b.as_slice()).is_err());
+
+        // bit 63 still fits
+        b[9] = 0x01;
+        assert_eq!(1 << 63, decode_u64(&b).unwrap().0);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..100_000 {
+            let r = xorshift(&mut state);
+            let mut b = r.to_le_bytes().to_vec();
+            b.extend_from_slice(&xorshift(&mut state).to_le_bytes());
+            // bias towards set continuation bits so long encodings are common
+            let mask = xorshift(&mut state) & CONTINUATION;
+            for (x, m) in b.iter_mut().zip(mask.to_le_bytes()) {
+                *x |= m;
+            }
+            for end in [b.len(), (r % 17) as usize] {
+                assert_same(&b[..end]);
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{error::VaruintError, varuint_decode, Error};
+use multibase::Base;
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+
+/// A type that is encoded as a [`crate::Varuint`] through an integer
+/// representation, e.g. an enum through its discriminant. The
+/// [`crate::varuint_repr`] macro implements it for fieldless enums, as does
+/// `#[derive(VaruintRepr)]` with the `derive` feature enabled.
+pub trait VaruintRepr: Sized {
+    /// the type the value is varuint encoded as, usually an unsigned integer.
+    /// its decoding errors are returned as [`Error::Multitrait`].
+    type Repr: EncodeInto + for<'a> TryDecodeFrom<'a, Error = multitrait::Error>;
+
+    /// convert to the representation
+    fn to_repr(&self) -> Self::Repr;
+
+    /// convert from the representation, failing with
+    /// [`VaruintError::UnknownDiscriminant`] if it does not map to a value
+    fn from_repr(repr: Self::Repr) -> Result<Self, Error>;
+
+    /// decode the representation from the front of the slice, returning the
+    /// remaining bytes. Overridden for the u64 and usize representations to
+    /// use the [`varuint_decode`] fast path.
+    fn try_decode_repr(bytes: &[u8]) -> Result<(Self::Repr, &[u8]), Error> {
+        Ok(Self::Repr::try_decode_from(bytes)?)
+    }
+}
+
+macro_rules! impl_varuint_repr_self {
+    ($($t:ty),*) => {
+        $(
+            impl VaruintRepr for $t {
+                type Repr = Self;
+
+                #[inline]
+                fn to_repr(&self) -> Self {
+                    *self
+                }
+
+                #[inline]
+                fn from_repr(repr: Self) -> Result<Self, Error> {
+                    Ok(repr)
+                }
+            }
+        )*
+    };
+    ($($t:ty => $decode:path),*) => {
+        $(
+            impl VaruintRepr for $t {
+                type Repr = Self;
+
+                #[inline]
+                fn to_repr(&self) -> Self {
+                    *self
+                }
+
+                #[inline]
+                fn from_repr(repr: Self) -> Result<Self, Error> {
+                    Ok(repr)
+                }
+
+                #[inline]
+                fn try_decode_repr(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+                    $decode(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_varuint_repr_self!(bool, u8, u16, u32, u128);
+impl_varuint_repr_self!(u64 => varuint_decode::decode_u64, usize => varuint_decode::decode_usize);
+
+/// Codecs are represented by their multicodec code
+impl VaruintRepr for Codec {
+    type Repr = u64;
+
+    fn to_repr(&self) -> u64 {
+        self.code()
+    }
+
+    fn from_repr(repr: u64) -> Result<Self, Error> {
+        Codec::try_from(repr).map_err(|_| unknown_discriminant(repr, "Codec"))
+    }
+
+    fn try_decode_repr(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+        u64::try_decode_repr(bytes)
+    }
+}
+
+/// Bases are represented by the code point of their multibase prefix
+impl VaruintRepr for Base {
+    type Repr = u32;
+
+    fn to_repr(&self) -> u32 {
+        self.code().into()
+    }
+
+    fn from_repr(repr: u32) -> Result<Self, Error> {
+        char::from_u32(repr)
+            .and_then(|c| Base::from_code(c).ok())
+            .ok_or_else(|| unknown_discriminant(repr, "Base"))
+    }
+}
+
+fn unknown_discriminant(repr: impl ToString, target: &'static str) -> Error {
+    VaruintError::UnknownDiscriminant {
+        value: repr.to_string(),
+        target,
+    }
+    .into()
+}
+
+/// Declares a fieldless enum with an explicit integer representation and
+/// implements [`VaruintRepr`] for it so that it can be wrapped in a
+/// [`crate::Varuint`]. Every variant needs an explicit discriminant.
+///
+/// ```
+/// use multiutil::{varuint_repr, Varuint};
+///
+/// varuint_repr! {
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     pub enum KeyType: u16 {
+///         Ed25519 = 0xed,
+///         Secp256k1 = 0xe7,
+///     }
+/// }
+///
+/// let v: Vec<u8> = Varuint(KeyType::Ed25519).into();
+/// assert_eq!(vec![0xed, 0x01], v);
+/// assert_eq!(KeyType::Ed25519, *Varuint::<KeyType>::try_from(v.as_slice()).unwrap());
+/// assert!(Varuint::<KeyType>::try_from([0x01].as_slice()).is_err());
+/// ```
+#[macro_export]
+macro_rules! varuint_repr {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident : $repr:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $value:expr),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr($repr)]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant = $value),+
+        }
+
+        impl $crate::VaruintRepr for $name {
+            type Repr = $repr;
+
+            fn to_repr(&self) -> $repr {
+                match self {
+                    $(Self::$variant => Self::$variant as $repr),+
+                }
+            }
+
+            fn from_repr(repr: $repr) -> Result<Self, $crate::Error> {
+                $(
+                    if repr == Self::$variant as $repr {
+                        return Ok(Self::$variant);
+                    }
+                )+
+                Err($crate::error::VaruintError::UnknownDiscriminant {
+                    value: repr.to_string(),
+                    target: stringify!($name),
+                }
+                .into())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, BaseIter};
+
+    varuint_repr! {
+        /// a test enum
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        enum Color: u64 {
+            Red = 1,
+            /// a variant with a large discriminant
+            Green = 0x4000,
+        }
+    }
+
+    #[test]
+    fn test_enum_round_trip() {
+        for c in [Color::Red, Color::Green] {
+            let v: Vec<u8> = Varuint(c).into();
+            assert_eq!(Varuint(c.to_repr()).encode_into(), v);
+            assert_eq!(
+                Varuint(c),
+                Varuint::<Color>::try_from(v.as_slice()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_discriminant() {
+        match Varuint::<Color>::try_from([0x02].as_slice()).unwrap_err() {
+            Error::Varuint(VaruintError::UnknownDiscriminant { value, target }) => {
+                assert_eq!("2", value);
+                assert_eq!("Color", target);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[derive(Clone, Copy, Debug, PartialEq, crate::VaruintRepr)]
+        #[repr(u16)]
+        enum KeyType {
+            Ed25519 = 0xed,
+            // an implicit discriminant follows the previous one
+            Ed448,
+        }
+
+        let v: Vec<u8> = Varuint(KeyType::Ed448).into();
+        assert_eq!(vec![0xee, 0x01], v);
+        for k in [KeyType::Ed25519, KeyType::Ed448] {
+            let v: Vec<u8> = Varuint(k).into();
+            assert_eq!(k, *Varuint::<KeyType>::try_from(v.as_slice()).unwrap());
+        }
+        match Varuint::<KeyType>::try_from([0x01].as_slice()).unwrap_err() {
+            Error::Varuint(VaruintError::UnknownDiscriminant { value, target }) => {
+                assert_eq!("1", value);
+                assert_eq!("KeyType", target);
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        // the decoder's errors are kept, not replaced with a custom error
+        let b = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02];
+        for e in [
+            Varuint::<u64>::try_from(b.as_slice()).unwrap_err(),
+            Varuint::<usize>::try_from(b.as_slice()).unwrap_err(),
+            Varuint::<Codec>::try_from(b.as_slice()).unwrap_err(),
+        ] {
+            assert!(matches!(e, Error::Varuint(VaruintError::Overflow { .. })));
+        }
+        assert!(matches!(
+            Varuint::<u64>::try_from([0x80].as_slice()).unwrap_err(),
+            Error::Multitrait(_)
+        ));
+
+        // and so are the multitrait errors of the other representations
+        for e in [
+            Varuint::<u8>::try_from([0x80].as_slice()).unwrap_err(),
+            Varuint::<Base>::try_from([0x80].as_slice()).unwrap_err(),
+            Varuint::<Color>::try_from([0x80].as_slice()).unwrap_err(),
+        ] {
+            assert!(matches!(e, Error::Multitrait(_)));
+        }
+    }
+
+    #[test]
+    fn test_codec() {
+        let v: Vec<u8> = Varuint(Codec::Ed25519Pub).into();
+        assert_eq!(vec![0xed, 0x01], v);
+        let (vu, rest) = Varuint::<Codec>::try_decode_from(&[0xed, 0x01, 0xff]).unwrap();
+        assert_eq!(Codec::Ed25519Pub, vu.to_inner());
+        assert_eq!(&[0xff], rest);
+        assert!(Codec::from_repr(0x7fff_ffff).is_err());
+    }
+
+    #[test]
+    fn test_base() {
+        for base in BaseIter::new() {
+            let v: Vec<u8> = Varuint(base).into();
+            assert_eq!(base, *Varuint::<Base>::try_from(v.as_slice()).unwrap());
+        }
+        assert_eq!(u32::from('z'), Base::Base58Btc.to_repr());
+        assert!(Base::from_repr(u32::from('!')).is_err());
+        assert!(Base::from_repr(0xd800).is_err());
+    }
+
+    #[test]
+    fn test_encoded() {
+        let v = EncodedVaruint::<Base>::from(Varuint(Base::Base58Btc));
+        let s = v.to_string();
+        assert_eq!("f7a", s);
+        assert_eq!(v, EncodedVaruint::<Base>::try_from(s.as_str()).unwrap());
+    }
+}
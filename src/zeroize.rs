@@ -0,0 +1,167 @@
+// SPDX-License-Idnetifier: Apache-2.0
+//! [`SecretVarbytes`], a [`crate::Varbytes`] for secret key material that is
+//! zeroized on drop and never printed
+use crate::{
+    decode_usize, encoded_len::check_len, error::VarbytesError, EncodedLen, Error, Varbytes,
+    Varuint,
+};
+use core::fmt;
+use multitrait::TryDecodeFrom;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// A varbytes holding secret data. The data is zeroized when dropped, Debug
+/// and Display are redacted and equality is constant-time.
+#[derive(Clone, Default)]
+pub struct SecretVarbytes(Vec<u8>);
+
+impl SecretVarbytes {
+    /// create a secret varbytes taking ownership of the data without copying
+    pub fn new(v: Vec<u8>) -> Self {
+        Self(v)
+    }
+
+    /// get a reference to the secret data
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// encode into a buffer that is zeroized when dropped. the buffer is
+    /// allocated at its final size so no partial copies are left behind.
+    pub fn encode_zeroizing(&self) -> Zeroizing<Vec<u8>> {
+        let mut v = Zeroizing::new(Vec::with_capacity(self.encoded_len()));
+        self.encode_extend(&mut v);
+        v
+    }
+}
+
+impl Zeroize for SecretVarbytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretVarbytes {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretVarbytes {}
+
+impl fmt::Debug for SecretVarbytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretVarbytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+impl fmt::Display for SecretVarbytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl PartialEq for SecretVarbytes {
+    /// compares with [`subtle::ConstantTimeEq`] in time that depends only on
+    /// the lengths, not the contents
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_slice().ct_eq(other.0.as_slice()).into()
+    }
+}
+
+impl Eq for SecretVarbytes {}
+
+impl From<Vec<u8>> for SecretVarbytes {
+    fn from(v: Vec<u8>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Varbytes> for SecretVarbytes {
+    fn from(vb: Varbytes) -> Self {
+        Self(vb.to_inner())
+    }
+}
+
+impl EncodedLen for SecretVarbytes {
+    fn encoded_len(&self) -> usize {
+        Varuint(self.0.len()).encoded_len() + self.0.len()
+    }
+
+    fn encode_to_slice(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        check_len(buf, self.encoded_len())?;
+        let prefix = Varuint(self.0.len()).encode_to_slice(buf)?;
+        let len = prefix + self.0.len();
+        buf[prefix..len].copy_from_slice(&self.0);
+        Ok(len)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SecretVarbytes {
+    type Error = Error;
+
+    fn try_from(s: &'a [u8]) -> Result<Self, Error> {
+        let (v, _) = Self::try_decode_from(s)?;
+        Ok(v)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SecretVarbytes {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (len, ptr) = decode_usize(bytes)?;
+        if ptr.len() < len {
+            return Err(VarbytesError::Truncated {
+                needed: len,
+                available: ptr.len(),
+            }
+            .into());
+        }
+        // to_vec allocates exactly len bytes so the data is copied only once
+        Ok((Self(ptr[..len].to_vec()), &ptr[len..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let s = SecretVarbytes::new(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!("SecretVarbytes([REDACTED; 4])", format!("{:?}", s));
+        assert_eq!("[REDACTED]", s.to_string());
+    }
+
+    #[test]
+    fn test_eq() {
+        let a = SecretVarbytes::new(vec![1, 2, 3]);
+        assert_eq!(a, SecretVarbytes::new(vec![1, 2, 3]));
+        assert_ne!(a, SecretVarbytes::new(vec![1, 2, 4]));
+        assert_ne!(a, SecretVarbytes::new(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut s = SecretVarbytes::new(vec![1, 2, 3]);
+        s.zeroize();
+        assert!(s.expose_secret().is_empty());
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let s1 = SecretVarbytes::from(Varbytes(vec![1, 2, 3]));
+        let b = s1.encode_zeroizing();
+        assert_eq!(&[3, 1, 2, 3], b.as_slice());
+        assert_eq!(b.len(), b.capacity());
+        let (s2, ptr) = SecretVarbytes::try_decode_from(&b).unwrap();
+        assert_eq!(s1, s2);
+        assert!(ptr.is_empty());
+    }
+
+    #[test]
+    fn test_truncated() {
+        assert!(SecretVarbytes::try_from([4, 1, 2].as_slice()).is_err());
+    }
+}